@@ -0,0 +1,35 @@
+//! Print the `BUNDLED` region table of `src/api/regions.rs` from the current
+//! catalog:
+//!
+//! ```sh
+//! cargo run --example bundled_regions
+//! ```
+use itad_api::{api::RegionCatalog, ItadApiClient};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = ItadApiClient::new()?;
+    let catalog = RegionCatalog::fetch(&client)?;
+
+    println!("const BUNDLED: &[BundledRegion] = &[");
+    for (region, info) in catalog.iter() {
+        let currency = &info.currency;
+        let countries: Vec<&str> = info.countries.iter().map(|c| c.as_str()).collect();
+        println!("    BundledRegion {{");
+        println!("        region: {:?},", region.as_str());
+        println!("        code: {:?},", currency.code);
+        println!("        sign: {:?},", currency.sign);
+        println!(
+            "        delimiter: {:?},",
+            currency.delimiter.as_deref().unwrap_or(".")
+        );
+        println!("        left: {:?},", currency.left);
+        println!(
+            "        name: {:?},",
+            currency.name.as_deref().unwrap_or(&currency.code)
+        );
+        println!("        countries: &{:?},", countries);
+        println!("    }},");
+    }
+    println!("];");
+    Ok(())
+}
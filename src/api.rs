@@ -1,18 +1,47 @@
+mod batch;
 mod client;
 pub mod collection;
 mod common;
 pub mod deals;
 mod endpoint;
 mod error;
+pub mod export;
 pub mod game;
+mod game_ref;
+mod ids;
+mod ignore;
+pub mod import;
+mod instrument;
+mod meta;
 mod query;
+mod raw;
+mod regions;
+mod render;
 pub mod search;
+#[cfg(feature = "tower")]
+mod service;
+mod shop;
 pub mod stats;
+#[cfg(test)]
+mod test_client;
 pub mod user;
 mod utils;
+pub mod v2;
 pub mod waitlist;
 pub mod web;
 
-pub use client::{AsyncClient, Client, RestClient};
+pub use batch::{batch, Batch};
+pub use client::{AsyncClient, Client, RestClient, Retries};
+pub use endpoint::Endpoint;
 pub use error::ApiError;
+pub use game_ref::GameRef;
+pub use ids::{Country, IdError, Plain, Region, ShopId};
+pub use ignore::{ignore, Ignore};
+pub use meta::{with_meta, AsyncQueryMeta, EndpointMeta, QueryMeta, Response, WithMeta};
 pub use query::{AsyncQuery, Query};
+pub use raw::{raw, Raw};
+pub use regions::{default_region, Currency, RegionCatalog, RegionInfo};
+pub use render::{render, RenderedRequest};
+#[cfg(feature = "tower")]
+pub use service::QueryService;
+pub use shop::{Shop, ShopCatalog, ShopInfo};
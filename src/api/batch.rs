@@ -0,0 +1,210 @@
+use std::time::{Duration, Instant};
+
+use futures::{Stream, StreamExt};
+use futures_timer::Delay;
+use serde::de::DeserializeOwned;
+
+use super::{endpoint::Endpoint, ApiError, AsyncClient, AsyncQuery};
+
+const DEFAULT_CONCURRENCY: usize = 8;
+/// The lowest rate `requests_per_second` accepts, one request a day. It keeps
+/// the delay before the last query well inside what `Duration` can hold.
+const MIN_REQUESTS_PER_SECOND: f64 = 1.0 / 86_400.0;
+
+/// Run many queries against an asynchronous client with bounded concurrency.
+///
+/// Each item of the resulting stream is the index of the endpoint in the input
+/// and its result, so a failing query does not end the batch.
+pub fn batch<C, I>(client: &C, endpoints: I) -> Batch<'_, C, I::Item>
+where
+    C: AsyncClient,
+    I: IntoIterator,
+{
+    Batch {
+        client,
+        endpoints: endpoints.into_iter().collect(),
+        concurrency: DEFAULT_CONCURRENCY,
+        requests_per_second: None,
+    }
+}
+
+#[derive(Debug)]
+pub struct Batch<'a, C, E> {
+    client: &'a C,
+    endpoints: Vec<E>,
+    concurrency: usize,
+    requests_per_second: Option<f64>,
+}
+
+impl<'a, C, E> Batch<'a, C, E>
+where
+    C: AsyncClient + Sync,
+    E: Endpoint + Sync + 'a,
+{
+    /// The maximum number of queries in flight at once. Defaults to 8.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Cap the rate at which queries are started, counted from when the
+    /// stream is first polled. Non-positive and NaN values remove the cap,
+    /// positive values below one request a day are raised to that.
+    pub fn requests_per_second(mut self, requests_per_second: f64) -> Self {
+        self.requests_per_second = Some(requests_per_second)
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| rate.max(MIN_REQUESTS_PER_SECOND));
+        self
+    }
+
+    /// Yield results in the order the endpoints were given.
+    pub fn ordered<T>(self) -> impl Stream<Item = (usize, Result<T, ApiError<C::Error>>)> + 'a
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let concurrency = self.concurrency;
+        self.queries().buffered(concurrency)
+    }
+
+    /// Yield results as soon as they complete.
+    pub fn unordered<T>(self) -> impl Stream<Item = (usize, Result<T, ApiError<C::Error>>)> + 'a
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let concurrency = self.concurrency;
+        self.queries().buffer_unordered(concurrency)
+    }
+
+    fn queries<T>(
+        self,
+    ) -> impl Stream<
+        Item = impl std::future::Future<Output = (usize, Result<T, ApiError<C::Error>>)> + 'a,
+    > + 'a
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let client = self.client;
+        let requests_per_second = self.requests_per_second;
+        let mut start = None;
+        futures::stream::iter(self.endpoints.into_iter().enumerate().map(
+            move |(index, endpoint)| {
+                // The first endpoint is taken on the first poll.
+                let start = *start.get_or_insert_with(Instant::now);
+                async move {
+                    if let Some(rate) = requests_per_second {
+                        let slot = Duration::from_secs_f64(index as f64 / rate);
+                        let elapsed = start.elapsed();
+                        if slot > elapsed {
+                            Delay::new(slot - elapsed).await;
+                        }
+                    }
+                    (index, endpoint.query_async(client).await)
+                }
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::api::test_client::{Get, TestClient, TestError};
+
+    fn client() -> TestClient {
+        TestClient::new()
+            .respond("slow/", r#"{ "data": 1 }"#)
+            .delay("slow/", Duration::from_millis(60))
+            .respond("fast/", r#"{ "data": 2 }"#)
+            .respond("medium/", r#"{ "data": 3 }"#)
+            .delay("medium/", Duration::from_millis(20))
+    }
+
+    fn endpoints() -> Vec<Get> {
+        vec![Get::new("slow/"), Get::new("fast/"), Get::new("medium/")]
+    }
+
+    fn values(results: Vec<(usize, Result<u32, ApiError<TestError>>)>) -> Vec<(usize, u32)> {
+        results
+            .into_iter()
+            .map(|(index, result)| (index, result.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn ordered_keeps_input_order() {
+        let client = client();
+        let results = block_on(batch(&client, endpoints()).ordered().collect::<Vec<_>>());
+        assert_eq!(values(results), vec![(0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn unordered_yields_as_completed() {
+        let client = client();
+        let results = block_on(batch(&client, endpoints()).unordered().collect::<Vec<_>>());
+        assert_eq!(values(results), vec![(1, 2), (2, 3), (0, 1)]);
+    }
+
+    #[test]
+    fn concurrency_bounds_queries_in_flight() {
+        let client = client();
+        let results = block_on(
+            batch(&client, endpoints())
+                .concurrency(1)
+                .unordered()
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(values(results), vec![(0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn failures_do_not_end_the_batch() {
+        let client = client();
+        let endpoints = vec![Get::new("fast/"), Get::new("missing/"), Get::new("fast/")];
+        let results: Vec<(usize, Result<u32, _>)> =
+            block_on(batch(&client, endpoints).ordered().collect());
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], (0, Ok(2))));
+        assert!(matches!(results[1], (1, Err(ApiError::Client { .. }))));
+        assert!(matches!(results[2], (2, Ok(2))));
+    }
+
+    #[test]
+    fn rate_is_clamped() {
+        let client = TestClient::new();
+        let rate = |rate: f64| {
+            batch(&client, Vec::<Get>::new())
+                .requests_per_second(rate)
+                .requests_per_second
+        };
+        assert_eq!(rate(2.0), Some(2.0));
+        assert_eq!(rate(1e-12), Some(MIN_REQUESTS_PER_SECOND));
+        assert_eq!(rate(0.0), None);
+        assert_eq!(rate(-1.0), None);
+        assert_eq!(rate(f64::NAN), None);
+    }
+
+    #[test]
+    fn rate_is_paced_from_the_first_poll() {
+        let client = TestClient::new().respond("fast/", r#"{ "data": 2 }"#);
+        let endpoints = vec![Get::new("fast/"), Get::new("fast/"), Get::new("fast/")];
+        let stream = batch(&client, endpoints)
+            .requests_per_second(10.0)
+            .unordered::<u32>();
+        // Slots that would have passed had pacing started with the stream.
+        std::thread::sleep(Duration::from_millis(250));
+        let polled = Instant::now();
+        let results: Vec<_> = block_on(stream.collect());
+        assert_eq!(results.len(), 3);
+
+        let offsets: Vec<Duration> = client
+            .requests()
+            .iter()
+            .map(|request| request.at - polled)
+            .collect();
+        assert!(offsets[0] < Duration::from_millis(50));
+        assert!(offsets[1] >= Duration::from_millis(100));
+        assert!(offsets[2] >= Duration::from_millis(200));
+    }
+}
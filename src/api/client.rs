@@ -2,10 +2,11 @@ use std::error::Error;
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use http::{request::Builder as RequestBuilder, Response};
+use http::{request::Builder as RequestBuilder, HeaderValue, Response};
 use url::{form_urlencoded::Serializer, Url, UrlQuery};
 
 use super::error::ApiError;
+use crate::auth::{AuthError, Scope};
 
 /// A trait representing a client which can communicate with IsThereAnyDeal via
 /// REST
@@ -27,6 +28,33 @@ pub trait RestClient {
         &self,
         query_params: &mut Serializer<'_, UrlQuery<'_>>,
     ) -> Result<(), ApiError<Self::Error>>;
+
+    /// The `Authorization` header value carrying the OAuth token.
+    fn oauth_bearer_header(&self) -> Result<HeaderValue, ApiError<Self::Error>> {
+        Err(AuthError::MissingOauthToken.into())
+    }
+
+    /// The scopes the OAuth token was granted, if known. Queries of endpoints
+    /// needing other scopes then fail without being sent.
+    fn oauth_token_scopes(&self) -> Option<&[Scope]> {
+        None
+    }
+}
+
+/// Response extension with the number of times a request was retried before
+/// the response was received. Clients or middleware that retry requests
+/// insert it, a response without it counts as not retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Retries(pub u32);
+
+impl Retries {
+    /// The retries recorded on `response`.
+    pub fn of<T>(response: &Response<T>) -> u32 {
+        response
+            .extensions()
+            .get::<Retries>()
+            .map_or(0, |retries| retries.0)
+    }
 }
 
 /// A trait representing a client which can communicate with IsThereAnyDeal
@@ -1,4 +1,4 @@
-use crate::api::{endpoint::Endpoint, error::BodyError};
+use crate::api::{endpoint::Endpoint, error::BodyError, import::CollectionImport, Plain, ShopId};
 use derive_builder::Builder;
 use http::Method;
 use serde::Serialize;
@@ -27,8 +27,8 @@ impl Display for CollectionCheckOptions {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct CollectionCheck<'a> {
-    plain: Cow<'a, str>,
-    #[builder(setter(name = "_optional"), private)]
+    plain: Plain<'a>,
+    #[builder(setter(name = "_optional"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     optional: BTreeSet<CollectionCheckOptions>,
@@ -105,7 +105,9 @@ impl Display for CollectionOptions {
 #[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
 #[serde(rename_all = "snake_case")]
 pub struct Collection<'a> {
-    shop: Option<Cow<'a, str>>,
+    #[builder(default)]
+    shop: Option<ShopId<'a>>,
+    #[builder(default)]
     short: Option<bool>,
     #[builder(setter(name = "_optional"), private)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
@@ -171,12 +173,10 @@ impl Endpoint for Collection<'_> {
     }
 }
 
-// TODO: Make body generic for T: Serialize?
 #[derive(Debug, Clone, Builder)]
 #[builder(setter(into, strip_option))]
 pub struct ImportCollectionViaForm<'a> {
-    #[doc = r"JSON encoded waitlist to import. See: https://itad.docs.apiary.io/#reference/collection/import-via-form/send-user-to-collection-import-form"]
-    body: Cow<'a, str>,
+    body: CollectionImport<'a>,
 }
 
 impl<'a> ImportCollectionViaForm<'a> {
@@ -198,13 +198,14 @@ impl Endpoint for ImportCollectionViaForm<'_> {
         #[derive(Debug, Serialize)]
         struct Body<'a> {
             file: Cow<'a, str>,
+            // `serde_urlencoded` cannot encode `()`, so the flag needs a value.
             #[serde(rename = "upload")]
-            _upload: (),
+            _upload: &'static str,
         }
-        let file = base64::encode(self.body.as_ref());
+        let file = base64::encode(serde_json::to_vec(&self.body)?);
         let body = Body {
             file: file.into(),
-            _upload: (),
+            _upload: "x",
         };
         Ok(Some((
             "application/x-www-form-urlencoded",
@@ -213,12 +214,11 @@ impl Endpoint for ImportCollectionViaForm<'_> {
     }
 }
 
-// TODO: Make file generic for T: Serialize?
 #[derive(Debug, Clone, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct ImportCollection<'a> {
-    file: Cow<'a, str>,
+    file: CollectionImport<'a>,
     #[builder(default, setter(skip))]
     #[serde(rename = "upload")]
     _upload: (),
@@ -240,7 +240,7 @@ impl Endpoint for ImportCollection<'_> {
     }
 
     fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, super::error::BodyError> {
-        Ok(Some(("application/json", self.file.as_bytes().to_owned())))
+        Ok(Some(("application/json", serde_json::to_vec(&self.file)?)))
     }
 
     fn requires_oauth_token(&self) -> bool {
@@ -2,9 +2,11 @@ use std::{borrow::Cow, collections::BTreeSet};
 
 use derive_builder::Builder;
 use http::Method;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::endpoint::Endpoint;
+use super::{
+    endpoint::Endpoint, meta::EndpointMeta, regions::check_bundled, Country, Region, ShopId,
+};
 
 #[derive(Debug, Clone)]
 pub enum Direction {
@@ -21,17 +23,22 @@ pub enum DealsSorting {
 }
 
 #[derive(Debug, Clone, Serialize, Builder)]
-#[builder(setter(into, strip_option))]
+#[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
 #[serde(rename_all = "snake_case")]
 pub struct DealsList<'a> {
+    #[builder(default)]
     offset: Option<usize>,
+    #[builder(default)]
     limit: Option<usize>,
-    region: Option<Cow<'a, str>>,
-    country: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_shops"), private)]
+    #[builder(default)]
+    region: Option<Region<'a>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_shops"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    shops: BTreeSet<Cow<'a, str>>,
+    shops: BTreeSet<ShopId<'a>>,
+    #[builder(default)]
     #[serde(serialize_with = "serialize_sorting")]
     sort: Option<DealsSorting>,
 }
@@ -45,7 +52,7 @@ impl<'a> DealsList<'a> {
 impl<'a> DealsListBuilder<'a> {
     pub fn shop<T>(&mut self, shop: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
@@ -56,13 +63,21 @@ impl<'a> DealsListBuilder<'a> {
     pub fn shops<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
             .extend(iter.map(Into::into));
         self
     }
+
+    fn validate(&self) -> Result<(), String> {
+        if let (Some(Some(region)), Some(Some(country))) = (&self.region, &self.country) {
+            check_bundled(region, country).map_err(|err| err.to_string())?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Endpoint for DealsList<'_> {
@@ -83,6 +98,20 @@ impl Endpoint for DealsList<'_> {
     }
 }
 
+/// Metadata returned alongside a deals list.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct DealsListMeta {
+    pub region: Option<String>,
+    pub country: Option<String>,
+    pub currency: Option<String>,
+    pub count: Option<usize>,
+    pub total: Option<usize>,
+}
+
+impl EndpointMeta for DealsList<'_> {
+    type Meta = DealsListMeta;
+}
+
 fn serialize_sorting<S>(value: &Option<DealsSorting>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -1,12 +1,13 @@
 use std::borrow::Cow;
 
 use async_trait::async_trait;
-use http::{header, Method, Request};
+use http::Method;
 use log::debug;
 use serde::de::DeserializeOwned;
 
+use crate::auth::Scope;
+
 use super::{
-    common::Root,
     error::BodyError,
     query::{self, AsyncQuery, Query},
     ApiError, AsyncClient, Client,
@@ -44,6 +45,23 @@ pub trait Endpoint {
     fn requires_oauth_token(&self) -> bool {
         false
     }
+
+    /// If the OAuth token is sent in an `Authorization: Bearer` header rather
+    /// than as a query parameter.
+    fn uses_bearer_token(&self) -> bool {
+        false
+    }
+
+    /// The OAuth scopes the token must have been granted.
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[]
+    }
+
+    /// If the response is wrapped in a `data` envelope. Endpoints of the
+    /// current API return their data unwrapped.
+    fn is_enveloped(&self) -> bool {
+        true
+    }
 }
 
 impl<E, T, C> Query<T, C> for E
@@ -52,39 +70,8 @@ where
     T: DeserializeOwned,
     C: Client,
 {
-    fn query(&self, client: &C) -> Result<T, super::ApiError<C::Error>> {
-        let mut url = client.rest_endpoint(&self.endpoint())?;
-        self.set_query_parameters(&mut url)?;
-        {
-            let mut query_params = url.query_pairs_mut();
-            if self.requires_api_key() {
-                client.append_api_key_query_param(&mut query_params)?;
-            }
-
-            if self.requires_oauth_token() {
-                client.append_oauth_token_query_param(&mut query_params)?;
-            }
-        }
-
-        let req = Request::builder()
-            .method(self.method())
-            .uri(query::url_to_http_uri(url));
-        let (req, data) = if let Some((mime, data)) = self.body()? {
-            let req = req.header(header::CONTENT_TYPE, mime);
-            (req, data)
-        } else {
-            (req, Vec::new())
-        };
-        let rsp = client.rest(req, data)?;
-        let status = rsp.status();
-        let value = serde_json::from_slice(rsp.body())?;
-        if !status.is_success() {
-            return Err(ApiError::from_itad_api(value));
-        }
-
-        serde_json::from_value::<Root<T>>(value)
-            .map(|root| root.data)
-            .map_err(ApiError::data_type::<T>)
+    fn query(&self, client: &C) -> Result<T, ApiError<C::Error>> {
+        query::decode_response(query::send(self, client)?, self.is_enveloped())
     }
 }
 
@@ -96,40 +83,6 @@ where
     C: AsyncClient + Sync,
 {
     async fn query_async(&self, client: &C) -> Result<T, ApiError<C::Error>> {
-        let mut url = client.rest_endpoint(&self.endpoint())?;
-        self.set_query_parameters(&mut url)?;
-        {
-            let mut query_params = url.query_pairs_mut();
-            if self.requires_api_key() {
-                client.append_api_key_query_param(&mut query_params)?;
-            }
-        }
-        {
-            if self.requires_oauth_token() {
-                let mut query_params = url.query_pairs_mut();
-                client.append_oauth_token_query_param(&mut query_params)?;
-            }
-        }
-
-        let req = Request::builder()
-            .method(self.method())
-            .uri(query::url_to_http_uri(url));
-        let (req, data) = if let Some((mime, data)) = self.body()? {
-            let req = req.header(header::CONTENT_TYPE, mime);
-            (req, data)
-        } else {
-            (req, Vec::new())
-        };
-
-        let rsp = client.rest_async(req, data).await?;
-        let status = rsp.status();
-        let value = serde_json::from_slice(rsp.body())?;
-        if !status.is_success() {
-            return Err(ApiError::from_itad_api(value));
-        }
-
-        serde_json::from_value::<Root<T>>(value)
-            .map(|value| value.data)
-            .map_err(ApiError::data_type::<T>)
+        query::decode_response(query::send_async(self, client).await?, self.is_enveloped())
     }
 }
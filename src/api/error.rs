@@ -74,6 +74,14 @@ where
         /// The name of the type that could not be deserialized.
         typename: &'static str,
     },
+    /// The game reference has no lookup to the requested kind of identifier
+    #[error("{} cannot be resolved to {}", reference, target)]
+    Unresolvable {
+        /// The game reference
+        reference: String,
+        /// The kind of identifier requested
+        target: &'static str,
+    },
     #[error("authentication error: {}", source)]
     Authentication {
         #[from]
@@ -98,6 +106,21 @@ where
             } else {
                 Self::Unknown { obj: val.clone() }
             }
+        } else if let Some(reason) = val.pointer("/reason_phrase").and_then(|v| v.as_str()) {
+            // The current API reports errors as the status with a list of details
+            let details: Vec<_> = val
+                .pointer("/details")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str())
+                .collect();
+            let msg = if details.is_empty() {
+                reason.into()
+            } else {
+                format!("{}: {}", reason, details.join(", "))
+            };
+            Self::ItadApi { msg }
         } else {
             Self::Unknown { obj: val.clone() }
         }
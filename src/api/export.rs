@@ -0,0 +1,1050 @@
+//! Back up the waitlist and collection as CSV or JSON.
+//!
+//! Rows have a fixed set of columns whether or not prices were joined, and
+//! can be read back and turned into import documents.
+//!
+//! Exports page through the waitlist or collection, `page_size` rows at a
+//! time, and join each page's prices with its own `Prices` queries. The
+//! `_pages` functions yield the pages as they are priced; the others collect
+//! them.
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    io::{Read, Write},
+};
+
+use derive_builder::Builder;
+use futures::{Stream, StreamExt, TryStreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use super::{
+    collection::{Collection, CollectionOptions},
+    game::Prices,
+    import::{
+        CollectionImport, CollectionImportBuilderError, CollectionImportEntry, ImportCopy,
+        WaitlistImport, WaitlistImportBuilderError, WaitlistImportEntry,
+    },
+    regions::check_bundled,
+    waitlist::{Waitlist, WaitlistOptions},
+    ApiError, AsyncClient, AsyncQuery, Client, Country, Plain, Query, Region, ShopId,
+};
+
+/// The most plains sent in one `Prices` query.
+const PLAINS_PER_QUERY: usize = 50;
+
+/// Errors that occur when reading or writing exports.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExportError {
+    #[error("CSV error: {}", source)]
+    Csv {
+        #[from]
+        source: csv::Error,
+    },
+    #[error("JSON error: {}", source)]
+    Json {
+        #[from]
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Debug, Clone, Default, Builder)]
+#[builder(
+    setter(into, strip_option),
+    default,
+    build_fn(validate = "Self::validate")
+)]
+pub struct ExportOptions<'a> {
+    /// Include game ids at this shop
+    shop: Option<ShopId<'a>>,
+    /// Join the current best price of each game
+    prices: bool,
+    region: Option<Region<'a>>,
+    country: Option<Country<'a>>,
+    /// Rows per page, 50 by default
+    page_size: Option<usize>,
+}
+
+impl<'a> ExportOptions<'a> {
+    pub fn builder() -> ExportOptionsBuilder<'a> {
+        ExportOptionsBuilder::default()
+    }
+
+    fn page_size(&self) -> usize {
+        self.page_size.unwrap_or(PLAINS_PER_QUERY)
+    }
+}
+
+impl ExportOptionsBuilder<'_> {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(0)) = self.page_size {
+            return Err("Page size must not be zero".into());
+        }
+
+        if let (Some(Some(region)), Some(Some(country))) = (&self.region, &self.country) {
+            check_bundled(region, country).map_err(|err| err.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaitlistRow {
+    pub plain: Plain<'static>,
+    pub title: Option<String>,
+    /// The shop `game_id` belongs to
+    pub shop: Option<ShopId<'static>>,
+    pub game_id: Option<String>,
+    /// Unix timestamp of when the game was waitlisted
+    pub added: Option<u64>,
+    pub price: Option<f64>,
+    pub regular: Option<f64>,
+    pub cut: Option<u32>,
+    pub price_shop: Option<ShopId<'static>>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionRow {
+    pub plain: Plain<'static>,
+    pub title: Option<String>,
+    /// The shop `game_id` belongs to
+    pub shop: Option<ShopId<'static>>,
+    pub game_id: Option<String>,
+    /// The shop the copy is owned at
+    pub copy_type: Option<ShopId<'static>>,
+    pub status: Option<String>,
+    pub owned: Option<bool>,
+    /// Unix timestamp of when the copy was added
+    pub added: Option<u64>,
+    pub note: Option<String>,
+    pub price: Option<f64>,
+    pub regular: Option<f64>,
+    pub cut: Option<u32>,
+    pub price_shop: Option<ShopId<'static>>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum WaitlistData {
+    Grouped(BTreeMap<String, Vec<WaitlistGame>>),
+    List(Vec<WaitlistGame>),
+}
+
+#[derive(Debug, Deserialize)]
+struct WaitlistGame {
+    plain: Plain<'static>,
+    title: Option<String>,
+    #[serde(rename = "gameid")]
+    game_id: Option<String>,
+    added: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionData {
+    #[serde(default)]
+    games: Vec<CollectionGame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionGame {
+    plain: Plain<'static>,
+    title: Option<String>,
+    #[serde(rename = "gameid")]
+    game_id: Option<String>,
+    #[serde(default)]
+    copies: Vec<CollectionCopy>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionCopy {
+    #[serde(rename = "type")]
+    copy_type: Option<ShopId<'static>>,
+    status: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_bool_or_int")]
+    owned: Option<bool>,
+    added: Option<u64>,
+    note: Option<String>,
+}
+
+fn deserialize_bool_or_int<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrInt {
+        Bool(bool),
+        Int(u8),
+    }
+
+    Ok(
+        Option::<BoolOrInt>::deserialize(deserializer)?.map(|value| match value {
+            BoolOrInt::Bool(value) => value,
+            BoolOrInt::Int(value) => value != 0,
+        }),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct GamePrices {
+    #[serde(default)]
+    list: Vec<Deal>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Deal {
+    price_new: f64,
+    price_old: f64,
+    price_cut: u32,
+    url: Option<String>,
+    shop: DealShop,
+}
+
+#[derive(Debug, Deserialize)]
+struct DealShop {
+    id: ShopId<'static>,
+}
+
+type BestPrices = BTreeMap<String, Deal>;
+
+/// A page of exported rows, or the error that stopped it.
+type Page<R, E> = Result<Vec<R>, ApiError<E>>;
+
+fn waitlist_endpoint<'a>(options: &ExportOptions<'a>) -> Waitlist<'a> {
+    let mut endpoint = Waitlist::builder();
+    endpoint.option(WaitlistOptions::Title);
+    if let Some(shop) = &options.shop {
+        endpoint.shop(shop.clone()).option(WaitlistOptions::Gameid);
+    }
+    endpoint.build().expect("no fields are required")
+}
+
+fn collection_endpoint<'a>(options: &ExportOptions<'a>) -> Collection<'a> {
+    let mut endpoint = Collection::builder();
+    endpoint
+        .option(CollectionOptions::Plain)
+        .option(CollectionOptions::Title)
+        .option(CollectionOptions::CopyType);
+    if let Some(shop) = &options.shop {
+        endpoint
+            .shop(shop.clone())
+            .option(CollectionOptions::Gameid);
+    }
+    endpoint.build().expect("plain is requested")
+}
+
+fn prices_endpoints<'a>(options: &ExportOptions<'a>, plains: &[Plain<'a>]) -> Vec<Prices<'a>> {
+    plains
+        .chunks(PLAINS_PER_QUERY)
+        .map(|chunk| {
+            let mut endpoint = Prices::builder();
+            endpoint.plains(chunk.iter().cloned());
+            if let Some(region) = &options.region {
+                endpoint.region(region.clone());
+            }
+            if let Some(country) = &options.country {
+                endpoint.country(country.clone());
+            }
+            endpoint.build().expect("region and country are checked")
+        })
+        .collect()
+}
+
+fn best_prices(chunks: Vec<BTreeMap<String, GamePrices>>) -> BestPrices {
+    chunks
+        .into_iter()
+        .flatten()
+        .filter_map(|(plain, prices)| {
+            let best = prices
+                .list
+                .into_iter()
+                .min_by(|a, b| a.price_new.total_cmp(&b.price_new))?;
+            Some((plain, best))
+        })
+        .collect()
+}
+
+fn waitlist_rows(data: WaitlistData, options: &ExportOptions<'_>) -> Vec<WaitlistRow> {
+    let games = match data {
+        WaitlistData::Grouped(groups) => groups.into_values().flatten().collect(),
+        WaitlistData::List(games) => games,
+    };
+    let shop = options.shop.clone().map(ShopId::into_owned);
+    games
+        .into_iter()
+        .map(|game| WaitlistRow {
+            plain: game.plain,
+            title: game.title,
+            shop: game.game_id.as_ref().and(shop.clone()),
+            game_id: game.game_id,
+            added: game.added,
+            price: None,
+            regular: None,
+            cut: None,
+            price_shop: None,
+            url: None,
+        })
+        .collect()
+}
+
+fn collection_rows(data: CollectionData, options: &ExportOptions<'_>) -> Vec<CollectionRow> {
+    let shop = options.shop.clone().map(ShopId::into_owned);
+    let mut rows = Vec::new();
+    for game in data.games {
+        let row = CollectionRow {
+            plain: game.plain,
+            title: game.title,
+            shop: game.game_id.as_ref().and(shop.clone()),
+            game_id: game.game_id,
+            copy_type: None,
+            status: None,
+            owned: None,
+            added: None,
+            note: None,
+            price: None,
+            regular: None,
+            cut: None,
+            price_shop: None,
+            url: None,
+        };
+        if game.copies.is_empty() {
+            rows.push(row);
+            continue;
+        }
+        rows.extend(game.copies.into_iter().map(|copy| CollectionRow {
+            copy_type: copy.copy_type,
+            status: copy.status,
+            owned: copy.owned,
+            added: copy.added,
+            note: copy.note,
+            ..row.clone()
+        }));
+    }
+    rows
+}
+
+/// An exported row the current best price of its game can be joined to.
+trait PricedRow {
+    fn plain(&self) -> &Plain<'static>;
+
+    fn set_price(&mut self, deal: &Deal);
+}
+
+impl PricedRow for WaitlistRow {
+    fn plain(&self) -> &Plain<'static> {
+        &self.plain
+    }
+
+    fn set_price(&mut self, deal: &Deal) {
+        self.price = Some(deal.price_new);
+        self.regular = Some(deal.price_old);
+        self.cut = Some(deal.price_cut);
+        self.price_shop = Some(deal.shop.id.clone());
+        self.url = deal.url.clone();
+    }
+}
+
+impl PricedRow for CollectionRow {
+    fn plain(&self) -> &Plain<'static> {
+        &self.plain
+    }
+
+    fn set_price(&mut self, deal: &Deal) {
+        self.price = Some(deal.price_new);
+        self.regular = Some(deal.price_old);
+        self.cut = Some(deal.price_cut);
+        self.price_shop = Some(deal.shop.id.clone());
+        self.url = deal.url.clone();
+    }
+}
+
+fn join_prices<R>(rows: &mut [R], prices: &BestPrices)
+where
+    R: PricedRow,
+{
+    for row in rows {
+        if let Some(deal) = prices.get(row.plain().as_str()) {
+            row.set_price(deal);
+        }
+    }
+}
+
+fn unique_plains<'a, I>(plains: I) -> Vec<Plain<'a>>
+where
+    I: Iterator<Item = &'a Plain<'static>>,
+{
+    let mut plains: Vec<Plain<'a>> = plains.cloned().collect();
+    plains.sort();
+    plains.dedup();
+    plains
+}
+
+fn query_prices<C>(
+    client: &C,
+    options: &ExportOptions<'_>,
+    plains: &[Plain<'_>],
+) -> Result<BestPrices, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let chunks = prices_endpoints(options, plains)
+        .iter()
+        .map(|endpoint| endpoint.query(client))
+        .collect::<Result<_, _>>()?;
+    Ok(best_prices(chunks))
+}
+
+async fn query_prices_async<C>(
+    client: &C,
+    options: &ExportOptions<'_>,
+    plains: &[Plain<'_>],
+) -> Result<BestPrices, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let endpoints = prices_endpoints(options, plains);
+    let chunks = futures::future::try_join_all(
+        endpoints
+            .iter()
+            .map(|endpoint| endpoint.query_async(client)),
+    )
+    .await?;
+    Ok(best_prices(chunks))
+}
+
+fn into_pages<R>(rows: Vec<R>, page_size: usize) -> Vec<Vec<R>> {
+    let mut rows = rows.into_iter().peekable();
+    let mut pages = Vec::new();
+    while rows.peek().is_some() {
+        pages.push(rows.by_ref().take(page_size).collect());
+    }
+    pages
+}
+
+fn with_prices<C, R>(client: &C, options: &ExportOptions<'_>, mut page: Vec<R>) -> Page<R, C::Error>
+where
+    C: Client,
+    R: PricedRow,
+{
+    if options.prices {
+        let plains = unique_plains(page.iter().map(PricedRow::plain));
+        let prices = query_prices(client, options, &plains)?;
+        join_prices(&mut page, &prices);
+    }
+    Ok(page)
+}
+
+async fn with_prices_async<C, R>(
+    client: &C,
+    options: &ExportOptions<'_>,
+    mut page: Vec<R>,
+) -> Page<R, C::Error>
+where
+    C: AsyncClient + Sync,
+    R: PricedRow,
+{
+    if options.prices {
+        let plains = unique_plains(page.iter().map(PricedRow::plain));
+        let prices = query_prices_async(client, options, &plains).await?;
+        join_prices(&mut page, &prices);
+    }
+    Ok(page)
+}
+
+fn pages<'a, C, R>(
+    client: &'a C,
+    options: &'a ExportOptions<'a>,
+    rows: Vec<R>,
+) -> impl Iterator<Item = Page<R, C::Error>> + 'a
+where
+    C: Client,
+    R: PricedRow + 'a,
+{
+    into_pages(rows, options.page_size())
+        .into_iter()
+        .map(move |page| with_prices(client, options, page))
+}
+
+fn pages_async<'a, C, R>(
+    client: &'a C,
+    options: &'a ExportOptions<'a>,
+    rows: Vec<R>,
+) -> impl Stream<Item = Page<R, C::Error>> + 'a
+where
+    C: AsyncClient + Sync,
+    R: PricedRow + 'a,
+{
+    futures::stream::iter(into_pages(rows, options.page_size()))
+        .then(move |page| with_prices_async(client, options, page))
+}
+
+/// Fetch the waitlist and page through it, with prices if `options` asks for
+/// them.
+pub fn export_waitlist_pages<'a, C>(
+    client: &'a C,
+    options: &'a ExportOptions<'a>,
+) -> Result<impl Iterator<Item = Page<WaitlistRow, C::Error>> + 'a, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let data = waitlist_endpoint(options).query(client)?;
+    Ok(pages(client, options, waitlist_rows(data, options)))
+}
+
+/// Fetch the waitlist and page through it asynchronously.
+pub async fn export_waitlist_pages_async<'a, C>(
+    client: &'a C,
+    options: &'a ExportOptions<'a>,
+) -> Result<impl Stream<Item = Page<WaitlistRow, C::Error>> + 'a, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let data = waitlist_endpoint(options).query_async(client).await?;
+    Ok(pages_async(client, options, waitlist_rows(data, options)))
+}
+
+/// Fetch the whole waitlist, with prices if `options` asks for them.
+pub fn export_waitlist<C>(
+    client: &C,
+    options: &ExportOptions<'_>,
+) -> Result<Vec<WaitlistRow>, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let mut rows = Vec::new();
+    for page in export_waitlist_pages(client, options)? {
+        rows.extend(page?);
+    }
+    Ok(rows)
+}
+
+/// Fetch the whole waitlist asynchronously.
+pub async fn export_waitlist_async<C>(
+    client: &C,
+    options: &ExportOptions<'_>,
+) -> Result<Vec<WaitlistRow>, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    export_waitlist_pages_async(client, options)
+        .await?
+        .try_concat()
+        .await
+}
+
+/// Fetch the collection and page through it, one row per copy, with prices
+/// if `options` asks for them.
+pub fn export_collection_pages<'a, C>(
+    client: &'a C,
+    options: &'a ExportOptions<'a>,
+) -> Result<impl Iterator<Item = Page<CollectionRow, C::Error>> + 'a, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let data = collection_endpoint(options).query(client)?;
+    Ok(pages(client, options, collection_rows(data, options)))
+}
+
+/// Fetch the collection and page through it asynchronously.
+pub async fn export_collection_pages_async<'a, C>(
+    client: &'a C,
+    options: &'a ExportOptions<'a>,
+) -> Result<impl Stream<Item = Page<CollectionRow, C::Error>> + 'a, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let data = collection_endpoint(options).query_async(client).await?;
+    Ok(pages_async(client, options, collection_rows(data, options)))
+}
+
+/// Fetch the whole collection, one row per copy, with prices if `options`
+/// asks for them.
+pub fn export_collection<C>(
+    client: &C,
+    options: &ExportOptions<'_>,
+) -> Result<Vec<CollectionRow>, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let mut rows = Vec::new();
+    for page in export_collection_pages(client, options)? {
+        rows.extend(page?);
+    }
+    Ok(rows)
+}
+
+/// Fetch the whole collection asynchronously.
+pub async fn export_collection_async<C>(
+    client: &C,
+    options: &ExportOptions<'_>,
+) -> Result<Vec<CollectionRow>, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    export_collection_pages_async(client, options)
+        .await?
+        .try_concat()
+        .await
+}
+
+pub fn write_csv<T, W>(rows: &[T], writer: W) -> Result<(), ExportError>
+where
+    T: Serialize,
+    W: Write,
+{
+    let mut writer = csv::Writer::from_writer(writer);
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush().map_err(csv::Error::from)?;
+    Ok(())
+}
+
+pub fn read_csv<T, R>(reader: R) -> Result<Vec<T>, ExportError>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    Ok(csv::Reader::from_reader(reader)
+        .deserialize()
+        .collect::<Result<_, _>>()?)
+}
+
+pub fn write_json<T, W>(rows: &[T], writer: W) -> Result<(), ExportError>
+where
+    T: Serialize,
+    W: Write,
+{
+    Ok(serde_json::to_writer_pretty(writer, rows)?)
+}
+
+pub fn read_json<T, R>(reader: R) -> Result<Vec<T>, ExportError>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// A waitlist import of exported rows.
+pub fn waitlist_import(
+    rows: &[WaitlistRow],
+) -> Result<WaitlistImport<'_>, WaitlistImportBuilderError> {
+    WaitlistImport::builder()
+        .source("itad")
+        .entries(rows.iter().map(|row| {
+            let mut entry = WaitlistImportEntry::builder();
+            entry.plain(row.plain.clone());
+            if let Some(title) = &row.title {
+                entry.title(title.as_str());
+            }
+            if let (Some(shop), Some(id)) = (&row.shop, &row.game_id) {
+                entry.game_id(shop.clone(), id.as_str());
+            }
+            entry.build().expect("plain is set")
+        }))
+        .build()
+}
+
+/// A collection import of exported rows, with one entry per plain.
+pub fn collection_import(
+    rows: &[CollectionRow],
+) -> Result<CollectionImport<'_>, CollectionImportBuilderError> {
+    let mut games: BTreeMap<&Plain<'static>, Vec<&CollectionRow>> = BTreeMap::new();
+    for row in rows {
+        games.entry(&row.plain).or_default().push(row);
+    }
+
+    CollectionImport::builder()
+        .source("itad")
+        .entries(games.into_iter().map(|(plain, rows)| {
+            let mut entry = CollectionImportEntry::builder();
+            entry.plain(plain.clone());
+            let first = rows[0];
+            if let Some(title) = &first.title {
+                entry.title(title.as_str());
+            }
+            if let (Some(shop), Some(id)) = (&first.shop, &first.game_id) {
+                entry.game_id(shop.clone(), id.as_str());
+            }
+            entry.copies(rows.iter().filter_map(|row| {
+                let mut copy = ImportCopy::builder();
+                copy.shop(row.copy_type.clone()?);
+                if let Some(status) = &row.status {
+                    copy.status(Cow::from(status.as_str()));
+                }
+                if let Some(owned) = row.owned {
+                    copy.owned(owned);
+                }
+                if let Some(added) = row.added {
+                    copy.added(added);
+                }
+                if let Some(note) = &row.note {
+                    copy.note(note.as_str());
+                }
+                Some(copy.build().expect("shop is set"))
+            }));
+            entry.build().expect("plain is set")
+        }))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::{json, to_value};
+
+    use super::*;
+    use crate::api::test_client::TestClient;
+
+    fn plain(plain: &str) -> Plain<'static> {
+        Plain::new(plain.to_owned()).unwrap()
+    }
+
+    fn shop(shop: &str) -> ShopId<'static> {
+        ShopId::new(shop.to_owned()).unwrap()
+    }
+
+    fn steam_options() -> ExportOptions<'static> {
+        ExportOptions::builder()
+            .shop(shop("steam"))
+            .build()
+            .unwrap()
+    }
+
+    fn waitlist_row(name: &str) -> WaitlistRow {
+        WaitlistRow {
+            plain: plain(name),
+            title: None,
+            shop: None,
+            game_id: None,
+            added: None,
+            price: None,
+            regular: None,
+            cut: None,
+            price_shop: None,
+            url: None,
+        }
+    }
+
+    const WAITLIST: &str = r#"{ "data": { "steam": [
+        { "plain": "portal", "title": "Portal", "gameid": "app/400", "added": 1500000000 },
+        { "plain": "braid", "title": "Braid, \"Deluxe\"", "added": 1600000000 }
+    ], "gog": [
+        { "plain": "witcher", "title": "The Witcher" }
+    ] } }"#;
+
+    const COLLECTION: &str = r#"{ "data": { "games": [
+        {
+            "plain": "portal",
+            "title": "Portal",
+            "gameid": "app/400",
+            "copies": [
+                { "type": "steam", "status": "redeemed", "owned": 1, "added": 1500000000 },
+                { "type": "gog", "owned": false, "note": "gift" }
+            ]
+        },
+        { "plain": "braid", "title": "Braid" }
+    ] } }"#;
+
+    const PRICES: &str = r#"{ "data": {
+        "portal": { "list": [
+            { "price_new": 4.99, "price_old": 9.99, "price_cut": 50, "url": "https://gog.test/portal", "shop": { "id": "gog" } },
+            { "price_new": 1.99, "price_old": 9.99, "price_cut": 80, "url": "https://steam.test/portal", "shop": { "id": "steam" } }
+        ] },
+        "braid": { "list": [] }
+    } }"#;
+
+    /// The `data` of a response body.
+    fn data<T>(body: &str) -> T
+    where
+        T: DeserializeOwned,
+    {
+        let value: serde_json::Value = serde_json::from_str(body).unwrap();
+        serde_json::from_value(value["data"].clone()).unwrap()
+    }
+
+    fn client() -> TestClient {
+        TestClient::new()
+            .respond("v01/user/wait/all/", WAITLIST)
+            .respond("v02/user/coll/all/", COLLECTION)
+            .respond("v01/game/prices/", PRICES)
+    }
+
+    #[test]
+    fn waitlist_rows_from_grouped_data() {
+        let data: WaitlistData = serde_json::from_value(json!({
+            "steam": [{ "plain": "portal", "gameid": "app/400", "added": 1 }],
+            "gog": [{ "plain": "witcher" }],
+        }))
+        .unwrap();
+        let rows = waitlist_rows(data, &steam_options());
+        assert_eq!(
+            rows,
+            vec![
+                waitlist_row("witcher"),
+                WaitlistRow {
+                    shop: Some(shop("steam")),
+                    game_id: Some("app/400".into()),
+                    added: Some(1),
+                    ..waitlist_row("portal")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn waitlist_rows_from_a_list() {
+        let data: WaitlistData =
+            serde_json::from_value(json!([{ "plain": "portal", "title": "Portal" }])).unwrap();
+        let rows = waitlist_rows(data, &ExportOptions::default());
+        assert_eq!(
+            rows,
+            vec![WaitlistRow {
+                title: Some("Portal".into()),
+                ..waitlist_row("portal")
+            }]
+        );
+    }
+
+    #[test]
+    fn collection_rows_per_copy() {
+        let data: CollectionData = data(COLLECTION);
+        let rows = collection_rows(data, &steam_options());
+        assert_eq!(rows.len(), 3);
+
+        assert_eq!(rows[0].plain, plain("portal"));
+        assert_eq!(rows[0].shop, Some(shop("steam")));
+        assert_eq!(rows[0].game_id.as_deref(), Some("app/400"));
+        assert_eq!(rows[0].copy_type, Some(shop("steam")));
+        assert_eq!(rows[0].status.as_deref(), Some("redeemed"));
+        assert_eq!(rows[0].owned, Some(true));
+        assert_eq!(rows[0].added, Some(1_500_000_000));
+
+        assert_eq!(rows[1].copy_type, Some(shop("gog")));
+        assert_eq!(rows[1].owned, Some(false));
+        assert_eq!(rows[1].note.as_deref(), Some("gift"));
+        assert_eq!(rows[1].title, rows[0].title);
+
+        assert_eq!(rows[2].plain, plain("braid"));
+        assert_eq!(rows[2].shop, None);
+        assert_eq!(rows[2].copy_type, None);
+    }
+
+    #[test]
+    fn joins_the_best_price() {
+        let prices = best_prices(vec![data(PRICES)]);
+        assert!(!prices.contains_key("braid"));
+
+        let mut rows = vec![waitlist_row("portal"), waitlist_row("braid")];
+        join_prices(&mut rows, &prices);
+        assert_eq!(
+            rows[0],
+            WaitlistRow {
+                price: Some(1.99),
+                regular: Some(9.99),
+                cut: Some(80),
+                price_shop: Some(shop("steam")),
+                url: Some("https://steam.test/portal".into()),
+                ..waitlist_row("portal")
+            }
+        );
+        assert_eq!(rows[1], waitlist_row("braid"));
+    }
+
+    #[test]
+    fn splits_rows_into_pages() {
+        let pages = into_pages((0..5).collect(), 2);
+        assert_eq!(pages, vec![vec![0, 1], vec![2, 3], vec![4]]);
+        assert!(into_pages(Vec::<u8>::new(), 2).is_empty());
+    }
+
+    #[test]
+    fn exports_the_waitlist_without_prices() {
+        let client = client();
+        let rows = export_waitlist(&client, &steam_options()).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.price.is_none()));
+        assert_eq!(client.requests().len(), 1);
+    }
+
+    #[test]
+    fn pages_the_waitlist_with_prices() {
+        let client = client();
+        let options = ExportOptions::builder()
+            .prices(true)
+            .page_size(2usize)
+            .build()
+            .unwrap();
+        let pages: Vec<Vec<WaitlistRow>> = export_waitlist_pages(&client, &options)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1]);
+        // One waitlist query, then one prices query per page.
+        assert_eq!(client.requests().len(), 3);
+
+        let portal = pages
+            .iter()
+            .flatten()
+            .find(|row| row.plain == plain("portal"))
+            .unwrap();
+        assert_eq!(portal.price, Some(1.99));
+        assert_eq!(portal.price_shop, Some(shop("steam")));
+
+        let rows = export_waitlist(&client, &options).unwrap();
+        assert_eq!(rows, pages.concat());
+        assert_eq!(
+            block_on(export_waitlist_async(&client, &options)).unwrap(),
+            rows
+        );
+    }
+
+    #[test]
+    fn pages_the_collection_with_prices() {
+        let client = client();
+        let options = ExportOptions::builder()
+            .prices(true)
+            .page_size(1usize)
+            .build()
+            .unwrap();
+        let rows = export_collection(&client, &options).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(client.requests().len(), 4);
+        assert!(rows[..2].iter().all(|row| row.price == Some(1.99)));
+        assert_eq!(rows[2].price, None);
+
+        let pages: Vec<Vec<CollectionRow>> = block_on(async {
+            export_collection_pages_async(&client, &options)
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+        })
+        .unwrap();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages.concat(), rows);
+        assert_eq!(
+            block_on(export_collection_async(&client, &options)).unwrap(),
+            rows
+        );
+    }
+
+    #[test]
+    fn price_errors_fail_the_page() {
+        let client = TestClient::new().respond("v01/user/wait/all/", WAITLIST);
+        let options = ExportOptions::builder().prices(true).build().unwrap();
+        let mut pages = export_waitlist_pages(&client, &options).unwrap();
+        assert!(pages.next().unwrap().is_err());
+        assert!(export_waitlist(&client, &options).is_err());
+        assert!(block_on(export_waitlist_async(&client, &options)).is_err());
+    }
+
+    #[test]
+    fn validates_options() {
+        assert!(ExportOptions::builder().page_size(0usize).build().is_err());
+        assert!(ExportOptions::builder()
+            .region(Region::new("us").unwrap())
+            .country(Country::new("DE").unwrap())
+            .build()
+            .is_err());
+        assert!(ExportOptions::builder()
+            .region(Region::new("eu1").unwrap())
+            .country(Country::new("DE").unwrap())
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let rows = export_waitlist(&client(), &steam_options()).unwrap();
+        let mut csv = Vec::new();
+        write_csv(&rows, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("plain,title,shop,game_id,added,price,regular,cut,price_shop,url")
+        );
+        assert!(csv.contains(r#"braid,"Braid, ""Deluxe""",,,1600000000,,,,,"#));
+        assert_eq!(read_csv::<WaitlistRow, _>(csv.as_bytes()).unwrap(), rows);
+    }
+
+    #[test]
+    fn csv_round_trip_with_prices() {
+        let options = ExportOptions::builder().prices(true).build().unwrap();
+        let rows = export_collection(&client(), &options).unwrap();
+        let mut csv = Vec::new();
+        write_csv(&rows, &mut csv).unwrap();
+        assert_eq!(read_csv::<CollectionRow, _>(&csv[..]).unwrap(), rows);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let options = ExportOptions::builder().prices(true).build().unwrap();
+        let rows = export_waitlist(&client(), &options).unwrap();
+        let mut json = Vec::new();
+        write_json(&rows, &mut json).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value[2]["title"], "Braid, \"Deluxe\"");
+        assert_eq!(read_json::<WaitlistRow, _>(&json[..]).unwrap(), rows);
+    }
+
+    #[test]
+    fn reading_bad_exports_fails() {
+        assert!(matches!(
+            read_csv::<WaitlistRow, _>(&b"plain\nNot A Plain\n"[..]),
+            Err(ExportError::Csv { .. })
+        ));
+        assert!(matches!(
+            read_json::<WaitlistRow, _>(&b"{}"[..]),
+            Err(ExportError::Json { .. })
+        ));
+    }
+
+    #[test]
+    fn waitlist_import_of_rows() {
+        let rows = export_waitlist(&client(), &steam_options()).unwrap();
+        assert_eq!(
+            to_value(waitlist_import(&rows).unwrap()).unwrap(),
+            json!({
+                "version": "02",
+                "source": "itad",
+                "data": [
+                    { "title": "The Witcher", "plain": "witcher" },
+                    { "title": "Portal", "plain": "portal", "gameid": ["steam", "app/400"] },
+                    { "title": "Braid, \"Deluxe\"", "plain": "braid" },
+                ],
+            })
+        );
+        assert!(waitlist_import(&[]).is_err());
+    }
+
+    #[test]
+    fn collection_import_of_rows() {
+        let rows = export_collection(&client(), &steam_options()).unwrap();
+        assert_eq!(
+            to_value(collection_import(&rows).unwrap()).unwrap(),
+            json!({
+                "version": "02",
+                "source": "itad",
+                "data": [
+                    { "title": "Braid", "plain": "braid" },
+                    {
+                        "title": "Portal",
+                        "plain": "portal",
+                        "gameid": ["steam", "app/400"],
+                        "copies": [
+                            { "type": "steam", "status": "redeemed", "owned": true, "added": 1500000000 },
+                            { "type": "gog", "owned": false, "note": "gift" },
+                        ],
+                    },
+                ],
+            })
+        );
+    }
+}
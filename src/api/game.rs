@@ -2,9 +2,11 @@ use std::{borrow::Cow, collections::BTreeSet, fmt::Display};
 
 use derive_builder::Builder;
 use http::Method;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use super::endpoint::Endpoint;
+use super::{
+    endpoint::Endpoint, meta::EndpointMeta, regions::check_bundled, Country, Plain, Region, ShopId,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum IdentifierOptions {
@@ -29,11 +31,15 @@ impl Display for IdentifierOptions {
 #[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
 #[serde(rename_all = "snake_case")]
 pub struct Identifier<'a> {
-    shop: Option<Cow<'a, str>>,
+    #[builder(default)]
+    shop: Option<ShopId<'a>>,
+    #[builder(default)]
     game_id: Option<Cow<'a, str>>,
+    #[builder(default)]
     url: Option<Cow<'a, str>>,
+    #[builder(default)]
     title: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_optional"), private)]
+    #[builder(setter(name = "_optional"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     optional: BTreeSet<IdentifierOptions>,
@@ -96,7 +102,7 @@ impl Endpoint for Identifier<'_> {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct MultiplePlainsById<'a> {
-    shop: Cow<'a, str>,
+    shop: ShopId<'a>,
     // This can be sent as a JSON array in a POST body, maybe do that instead?
     #[builder(setter(name = "_ids"), private)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
@@ -153,7 +159,7 @@ impl Endpoint for MultiplePlainsById<'_> {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct AllPlains<'a> {
-    shop: Cow<'a, str>,
+    shop: ShopId<'a>,
 }
 
 impl<'a> AllPlains<'a> {
@@ -193,7 +199,8 @@ pub enum MapType {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct IdPlainMap<'a> {
-    shop: Cow<'a, str>,
+    shop: ShopId<'a>,
+    #[builder(default)]
     #[serde(rename = "type")]
     type_field: Option<MapType>,
 }
@@ -222,23 +229,34 @@ impl Endpoint for IdPlainMap<'_> {
     }
 }
 
+/// Metadata returned alongside price data.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct PriceMeta {
+    pub region: Option<String>,
+    pub country: Option<String>,
+    pub currency: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Builder)]
-#[builder(setter(into, strip_option))]
+#[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
 #[serde(rename_all = "snake_case")]
 pub struct Prices<'a> {
     #[builder(setter(name = "_plains"), private)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
-    plains: BTreeSet<Cow<'a, str>>,
-    region: Option<Cow<'a, str>>,
-    country: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_shops"), private)]
+    plains: BTreeSet<Plain<'a>>,
+    #[builder(default)]
+    region: Option<Region<'a>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_shops"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    shops: BTreeSet<Cow<'a, str>>,
-    #[builder(setter(name = "_exclude"), private)]
+    shops: BTreeSet<ShopId<'a>>,
+    #[builder(setter(name = "_exclude"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    exclude: BTreeSet<Cow<'a, str>>,
+    exclude: BTreeSet<ShopId<'a>>,
+    #[builder(default)]
     added: Option<Cow<'a, str>>,
 }
 
@@ -251,7 +269,7 @@ impl<'a> Prices<'a> {
 impl<'a> PricesBuilder<'a> {
     pub fn plain<T>(&mut self, plain: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -262,7 +280,7 @@ impl<'a> PricesBuilder<'a> {
     pub fn plains<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -272,7 +290,7 @@ impl<'a> PricesBuilder<'a> {
 
     pub fn shop<T>(&mut self, shop: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
@@ -283,7 +301,7 @@ impl<'a> PricesBuilder<'a> {
     pub fn shops<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
@@ -293,7 +311,7 @@ impl<'a> PricesBuilder<'a> {
 
     pub fn exclude<T>(&mut self, exclude: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.exclude
             .get_or_insert_with(BTreeSet::new)
@@ -304,13 +322,21 @@ impl<'a> PricesBuilder<'a> {
     pub fn excludes<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.exclude
             .get_or_insert_with(BTreeSet::new)
             .extend(iter.map(Into::into));
         self
     }
+
+    fn validate(&self) -> Result<(), String> {
+        if let (Some(Some(region)), Some(Some(country))) = (&self.region, &self.country) {
+            check_bundled(region, country).map_err(|err| err.to_string())?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Endpoint for Prices<'_> {
@@ -331,25 +357,34 @@ impl Endpoint for Prices<'_> {
     }
 }
 
+impl EndpointMeta for Prices<'_> {
+    type Meta = PriceMeta;
+}
+
 #[derive(Debug, Clone, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct HistoricalLow<'a> {
     #[builder(setter(name = "_plains"), private)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
-    plains: BTreeSet<Cow<'a, str>>,
-    region: Option<Cow<'a, str>>,
-    country: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_shops"), private)]
+    plains: BTreeSet<Plain<'a>>,
+    #[builder(default)]
+    region: Option<Region<'a>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_shops"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    shops: BTreeSet<Cow<'a, str>>,
-    #[builder(setter(name = "_exclude"), private)]
+    shops: BTreeSet<ShopId<'a>>,
+    #[builder(setter(name = "_exclude"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    exclude: BTreeSet<Cow<'a, str>>,
+    exclude: BTreeSet<ShopId<'a>>,
+    #[builder(default)]
     since: Option<u64>,
+    #[builder(default)]
     until: Option<u64>,
+    #[builder(default)]
     new: Option<bool>,
 }
 
@@ -362,7 +397,7 @@ impl<'a> HistoricalLow<'a> {
 impl<'a> HistoricalLowBuilder<'a> {
     pub fn plain<T>(&mut self, plain: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -373,7 +408,7 @@ impl<'a> HistoricalLowBuilder<'a> {
     pub fn plains<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -383,7 +418,7 @@ impl<'a> HistoricalLowBuilder<'a> {
 
     pub fn shop<T>(&mut self, shop: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
@@ -394,7 +429,7 @@ impl<'a> HistoricalLowBuilder<'a> {
     pub fn shops<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
@@ -404,7 +439,7 @@ impl<'a> HistoricalLowBuilder<'a> {
 
     pub fn exclude<T>(&mut self, exclude: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.exclude
             .get_or_insert_with(BTreeSet::new)
@@ -415,7 +450,7 @@ impl<'a> HistoricalLowBuilder<'a> {
     pub fn excludes<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.exclude
             .get_or_insert_with(BTreeSet::new)
@@ -442,23 +477,29 @@ impl Endpoint for HistoricalLow<'_> {
     }
 }
 
+impl EndpointMeta for HistoricalLow<'_> {
+    type Meta = PriceMeta;
+}
+
 #[derive(Debug, Clone, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct StoreLow<'a> {
     #[builder(setter(name = "_plains"), private)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
-    plains: BTreeSet<Cow<'a, str>>,
-    region: Option<Cow<'a, str>>,
-    country: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_shops"), private)]
+    plains: BTreeSet<Plain<'a>>,
+    #[builder(default)]
+    region: Option<Region<'a>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_shops"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    shops: BTreeSet<Cow<'a, str>>,
-    #[builder(setter(name = "_exclude"), private)]
+    shops: BTreeSet<ShopId<'a>>,
+    #[builder(setter(name = "_exclude"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    exclude: BTreeSet<Cow<'a, str>>,
+    exclude: BTreeSet<ShopId<'a>>,
 }
 
 impl<'a> StoreLow<'a> {
@@ -470,7 +511,7 @@ impl<'a> StoreLow<'a> {
 impl<'a> StoreLowBuilder<'a> {
     pub fn plain<T>(&mut self, plain: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -481,7 +522,7 @@ impl<'a> StoreLowBuilder<'a> {
     pub fn plains<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -491,7 +532,7 @@ impl<'a> StoreLowBuilder<'a> {
 
     pub fn shop<T>(&mut self, shop: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
@@ -502,7 +543,7 @@ impl<'a> StoreLowBuilder<'a> {
     pub fn shops<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.shops
             .get_or_insert_with(BTreeSet::new)
@@ -512,7 +553,7 @@ impl<'a> StoreLowBuilder<'a> {
 
     pub fn exclude<T>(&mut self, exclude: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.exclude
             .get_or_insert_with(BTreeSet::new)
@@ -523,7 +564,7 @@ impl<'a> StoreLowBuilder<'a> {
     pub fn excludes<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.exclude
             .get_or_insert_with(BTreeSet::new)
@@ -550,6 +591,10 @@ impl Endpoint for StoreLow<'_> {
     }
 }
 
+impl EndpointMeta for StoreLow<'_> {
+    type Meta = PriceMeta;
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BundlesSorting {
@@ -563,11 +608,15 @@ pub enum BundlesSorting {
 pub struct Bundles<'a> {
     #[builder(setter(name = "_plains"), private)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
-    plains: BTreeSet<Cow<'a, str>>,
+    plains: BTreeSet<Plain<'a>>,
+    #[builder(default)]
     limit: Option<i64>,
+    #[builder(default)]
     expired: Option<bool>,
+    #[builder(default)]
     sort: Option<BundlesSorting>,
-    region: Option<Cow<'a, str>>,
+    #[builder(default)]
+    region: Option<Region<'a>>,
 }
 
 impl<'a> Bundles<'a> {
@@ -579,7 +628,7 @@ impl<'a> Bundles<'a> {
 impl<'a> BundlesBuilder<'a> {
     pub fn plain<T>(&mut self, plain: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -590,7 +639,7 @@ impl<'a> BundlesBuilder<'a> {
     pub fn plains<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -638,8 +687,8 @@ impl Display for InfoOptions {
 pub struct Info<'a> {
     #[builder(setter(name = "_plains"), private)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
-    plains: BTreeSet<Cow<'a, str>>,
-    #[builder(setter(name = "_optional"), private)]
+    plains: BTreeSet<Plain<'a>>,
+    #[builder(setter(name = "_optional"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     optional: BTreeSet<InfoOptions>,
@@ -654,7 +703,7 @@ impl<'a> Info<'a> {
 impl<'a> InfoBuilder<'a> {
     pub fn plain<T>(&mut self, plain: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -665,7 +714,7 @@ impl<'a> InfoBuilder<'a> {
     pub fn plains<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -732,22 +781,25 @@ impl Display for OverviewOptions {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct Overview<'a> {
-    region: Option<Cow<'a, str>>,
-    country: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_plains"), private)]
+    #[builder(default)]
+    region: Option<Region<'a>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_plains"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    plains: BTreeSet<Cow<'a, str>>,
-    shop: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_ids"), private)]
+    plains: BTreeSet<Plain<'a>>,
+    #[builder(default)]
+    shop: Option<ShopId<'a>>,
+    #[builder(setter(name = "_ids"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     ids: BTreeSet<Cow<'a, str>>,
-    #[builder(setter(name = "_allowed"), private)]
+    #[builder(setter(name = "_allowed"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    allowed: BTreeSet<Cow<'a, str>>,
-    #[builder(setter(name = "_optional"), private)]
+    allowed: BTreeSet<ShopId<'a>>,
+    #[builder(setter(name = "_optional"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     optional: BTreeSet<OverviewOptions>,
@@ -762,7 +814,7 @@ impl<'a> Overview<'a> {
 impl<'a> OverviewBuilder<'a> {
     pub fn plain<T>(&mut self, plain: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -773,7 +825,7 @@ impl<'a> OverviewBuilder<'a> {
     pub fn plains<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -802,7 +854,7 @@ impl<'a> OverviewBuilder<'a> {
 
     pub fn allowed<T>(&mut self, allowed: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.allowed
             .get_or_insert_with(BTreeSet::new)
@@ -813,7 +865,7 @@ impl<'a> OverviewBuilder<'a> {
     pub fn alloweds<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<ShopId<'a>>,
     {
         self.allowed
             .get_or_insert_with(BTreeSet::new)
@@ -854,3 +906,7 @@ impl Endpoint for Overview<'_> {
         true
     }
 }
+
+impl EndpointMeta for Overview<'_> {
+    type Meta = PriceMeta;
+}
@@ -0,0 +1,252 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt::Display};
+
+use serde::Deserialize;
+
+use super::{
+    game::Identifier,
+    v2::{game::Lookup, game::LookupResult, lookup::GameIdsByShopId},
+    ApiError, AsyncClient, AsyncQuery, Client, Plain, Query, ShopId,
+};
+
+/// A reference to a game, in any of the ways the legacy and current APIs
+/// identify games.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameRef<'a> {
+    /// Legacy plain
+    Plain(Plain<'a>),
+    /// Current API game id (UUID)
+    Id(Cow<'a, str>),
+    /// Game id at a shop, with the shop's current API id
+    ShopId {
+        shop: u32,
+        id: Cow<'a, str>,
+    },
+    Title(Cow<'a, str>),
+    /// Store page URL, with the shop's legacy id
+    Url {
+        shop: ShopId<'a>,
+        url: Cow<'a, str>,
+    },
+}
+
+impl Display for GameRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameRef::Plain(plain) => write!(f, "plain {}", plain),
+            GameRef::Id(id) => write!(f, "game id {}", id),
+            GameRef::ShopId { shop, id } => write!(f, "shop {} id {}", shop, id),
+            GameRef::Title(title) => write!(f, "title {:?}", title),
+            GameRef::Url { shop, url } => write!(f, "shop {} url {}", shop, url),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PlainResult {
+    plain: Option<Plain<'static>>,
+}
+
+enum IdLookup<'a> {
+    Found(String),
+    Lookup(Lookup<'a>),
+    ShopId(GameIdsByShopId<'a>, &'a str),
+}
+
+enum PlainLookup<'a> {
+    Found(Plain<'static>),
+    Identifier(Identifier<'a>),
+}
+
+impl<'a> GameRef<'a> {
+    fn unresolvable<E>(&self, target: &'static str) -> ApiError<E>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        ApiError::Unresolvable {
+            reference: self.to_string(),
+            target,
+        }
+    }
+
+    fn id_lookup<E>(&'a self) -> Result<IdLookup<'a>, ApiError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Ok(match self {
+            GameRef::Id(id) => IdLookup::Found(id.to_string()),
+            GameRef::Title(title) => IdLookup::Lookup(
+                Lookup::builder()
+                    .title(title.as_ref())
+                    .build()
+                    .expect("title is set"),
+            ),
+            GameRef::ShopId { shop, id } => IdLookup::ShopId(
+                GameIdsByShopId::builder()
+                    .shop(*shop)
+                    .id(id.as_ref())
+                    .build()
+                    .expect("shop and id are set"),
+                id,
+            ),
+            GameRef::Plain(_) | GameRef::Url { .. } => {
+                return Err(self.unresolvable("a game id"));
+            }
+        })
+    }
+
+    fn plain_lookup<E>(&'a self) -> Result<PlainLookup<'a>, ApiError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Ok(match self {
+            GameRef::Plain(plain) => PlainLookup::Found(plain.clone().into_owned()),
+            GameRef::Title(title) => PlainLookup::Identifier(
+                Identifier::builder()
+                    .title(title.as_ref())
+                    .build()
+                    .expect("title is set"),
+            ),
+            GameRef::Url { shop, url } => PlainLookup::Identifier(
+                Identifier::builder()
+                    .shop(shop.clone())
+                    .url(url.as_ref())
+                    .build()
+                    .expect("shop and url are set"),
+            ),
+            GameRef::Id(_) | GameRef::ShopId { .. } => {
+                return Err(self.unresolvable("a plain"));
+            }
+        })
+    }
+
+    /// Resolve to a current API game id, as used by the `v2` endpoints.
+    /// Returns `None` if no game matched.
+    pub fn resolve_id<C>(&self, client: &C) -> Result<Option<String>, ApiError<C::Error>>
+    where
+        C: Client,
+    {
+        match self.id_lookup()? {
+            IdLookup::Found(id) => Ok(Some(id)),
+            IdLookup::Lookup(endpoint) => {
+                let result: LookupResult = endpoint.query(client)?;
+                Ok(result.game.map(|game| game.id))
+            }
+            IdLookup::ShopId(endpoint, id) => {
+                let mut ids: BTreeMap<String, Option<String>> = endpoint.query(client)?;
+                Ok(ids.remove(id).flatten())
+            }
+        }
+    }
+
+    /// Resolve to a current API game id asynchronously.
+    pub async fn resolve_id_async<C>(
+        &self,
+        client: &C,
+    ) -> Result<Option<String>, ApiError<C::Error>>
+    where
+        C: AsyncClient + Sync,
+    {
+        match self.id_lookup()? {
+            IdLookup::Found(id) => Ok(Some(id)),
+            IdLookup::Lookup(endpoint) => {
+                let result: LookupResult = endpoint.query_async(client).await?;
+                Ok(result.game.map(|game| game.id))
+            }
+            IdLookup::ShopId(endpoint, id) => {
+                let mut ids: BTreeMap<String, Option<String>> =
+                    endpoint.query_async(client).await?;
+                Ok(ids.remove(id).flatten())
+            }
+        }
+    }
+
+    /// Resolve to a legacy plain, as used by the legacy endpoints. Returns
+    /// `None` if no game matched.
+    pub fn resolve_plain<C>(&self, client: &C) -> Result<Option<Plain<'static>>, ApiError<C::Error>>
+    where
+        C: Client,
+    {
+        match self.plain_lookup()? {
+            PlainLookup::Found(plain) => Ok(Some(plain)),
+            PlainLookup::Identifier(endpoint) => {
+                let result: PlainResult = endpoint.query(client)?;
+                Ok(result.plain)
+            }
+        }
+    }
+
+    /// Resolve to a legacy plain asynchronously.
+    pub async fn resolve_plain_async<C>(
+        &self,
+        client: &C,
+    ) -> Result<Option<Plain<'static>>, ApiError<C::Error>>
+    where
+        C: AsyncClient + Sync,
+    {
+        match self.plain_lookup()? {
+            PlainLookup::Found(plain) => Ok(Some(plain)),
+            PlainLookup::Identifier(endpoint) => {
+                let result: PlainResult = endpoint.query_async(client).await?;
+                Ok(result.plain)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_client::TestClient;
+
+    #[test]
+    fn resolve_plain_of_a_plain() {
+        let plain = Plain::new("portal").unwrap();
+        let resolved = GameRef::Plain(plain.clone())
+            .resolve_plain(&TestClient::new())
+            .unwrap();
+        assert_eq!(resolved, Some(plain));
+    }
+
+    #[test]
+    fn resolve_plain_of_a_title() {
+        let client = TestClient::new().respond(
+            "v02/game/plain/",
+            r#"{ "data": { "found": true, "plain": "portal" } }"#,
+        );
+        let resolved = GameRef::Title("Portal".into())
+            .resolve_plain(&client)
+            .unwrap();
+        assert_eq!(resolved, Some(Plain::new("portal").unwrap()));
+
+        let client =
+            TestClient::new().respond("v02/game/plain/", r#"{ "data": { "found": false } }"#);
+        let resolved = GameRef::Title("Portal 3".into())
+            .resolve_plain(&client)
+            .unwrap();
+        assert_eq!(resolved, None);
+    }
+
+    #[test]
+    fn resolve_plain_rejects_invalid_plains() {
+        let client =
+            TestClient::new().respond("v02/game/plain/", r#"{ "data": { "plain": "Portal 2" } }"#);
+        let err = GameRef::Title("Portal 2".into())
+            .resolve_plain(&client)
+            .unwrap_err();
+        assert!(matches!(err, ApiError::DataType { .. }));
+    }
+
+    #[test]
+    fn resolve_plain_of_an_id() {
+        let err = GameRef::Id("018d937f-21e1-728e-86d7-9acb3c59f2bb".into())
+            .resolve_plain(&TestClient::new())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Unresolvable {
+                target: "a plain",
+                ..
+            }
+        ));
+    }
+}
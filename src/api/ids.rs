@@ -0,0 +1,217 @@
+use std::{
+    borrow::{Borrow, Cow},
+    convert::TryFrom,
+    fmt::Display,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Errors that occur when validating identifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum IdError {
+    #[error("invalid {}: {:?}", kind, value)]
+    Invalid {
+        /// The kind of identifier
+        kind: &'static str,
+        /// The rejected value
+        value: String,
+    },
+    #[error("country {} is not in region {}", country, region)]
+    CountryNotInRegion {
+        /// The rejected country
+        country: String,
+        /// The region it was paired with
+        region: String,
+    },
+}
+
+fn check_lower_alnum(value: Cow<'_, str>) -> Result<Cow<'_, str>, Cow<'_, str>> {
+    if !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    {
+        Ok(value)
+    } else {
+        Err(value)
+    }
+}
+
+fn check_country(value: Cow<'_, str>) -> Result<Cow<'_, str>, Cow<'_, str>> {
+    if value.len() != 2 || !value.bytes().all(|b| b.is_ascii_alphabetic()) {
+        Err(value)
+    } else if value.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(value)
+    } else {
+        Ok(value.to_ascii_uppercase().into())
+    }
+}
+
+macro_rules! identifier {
+    ($(#[$meta:meta])* $name:ident, $kind:literal, $check:path) => {
+        $(#[$meta])*
+        ///
+        /// Builders take this type; convert a `&str` or `String` with `new`,
+        /// `parse` or `TryFrom`, which return an `IdError` for invalid values.
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a>(Cow<'a, str>);
+
+        impl<'a> $name<'a> {
+            pub fn new<S>(value: S) -> Result<Self, IdError>
+            where
+                S: Into<Cow<'a, str>>,
+            {
+                $check(value.into())
+                    .map(Self)
+                    .map_err(|value| IdError::Invalid {
+                        kind: $kind,
+                        value: value.into_owned(),
+                    })
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_owned(self) -> $name<'static> {
+                $name(Cow::Owned(self.0.into_owned()))
+            }
+        }
+
+        impl AsRef<str> for $name<'_> {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name<'_> {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Display for $name<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name<'static> {
+            type Err = IdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $name::new(s.to_owned())
+            }
+        }
+
+        impl<'a> TryFrom<&'a str> for $name<'a> {
+            type Error = IdError;
+
+            fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+                $name::new(value)
+            }
+        }
+
+        impl TryFrom<String> for $name<'_> {
+            type Error = IdError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                $name::new(value)
+            }
+        }
+
+        impl<'a> From<$name<'a>> for Cow<'a, str> {
+            fn from(value: $name<'a>) -> Self {
+                value.0
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name<'_> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                $name::new(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+            }
+        }
+
+        impl Serialize for $name<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(&self.0)
+            }
+        }
+    };
+}
+
+identifier!(
+    /// Legacy game identifier, such as `witcheriiiwildhunt`.
+    Plain,
+    "plain",
+    check_lower_alnum
+);
+identifier!(
+    /// Legacy shop identifier, such as `steam`.
+    ShopId,
+    "shop id",
+    check_lower_alnum
+);
+identifier!(
+    /// ITAD region, such as `eu1` or `us`.
+    Region,
+    "region",
+    check_lower_alnum
+);
+identifier!(
+    /// ISO 3166-1 alpha-2 country code, normalized to uppercase.
+    Country,
+    "country",
+    check_country
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_identifiers() {
+        assert_eq!(Plain::new("halflifeii").unwrap().as_str(), "halflifeii");
+        assert_eq!(ShopId::try_from("steam").unwrap().as_str(), "steam");
+        assert_eq!(
+            Region::try_from(String::from("eu1")).unwrap().as_str(),
+            "eu1"
+        );
+        assert_eq!("de".parse::<Country<'_>>().unwrap().as_str(), "DE");
+    }
+
+    #[test]
+    fn invalid_identifiers() {
+        assert_eq!(
+            Plain::try_from("Half-Life"),
+            Err(IdError::Invalid {
+                kind: "plain",
+                value: "Half-Life".into(),
+            })
+        );
+        assert!(ShopId::try_from(String::new()).is_err());
+        assert!("eu 1".parse::<Region<'_>>().is_err());
+        assert!(Country::new("deu").is_err());
+        assert!(Country::new("d1").is_err());
+    }
+
+    #[test]
+    fn serde_validates() {
+        let plain: Plain<'_> = serde_json::from_str(r#""portal""#).unwrap();
+        assert_eq!(serde_json::to_string(&plain).unwrap(), r#""portal""#);
+        let country: Country<'_> = serde_json::from_str(r#""us""#).unwrap();
+        assert_eq!(country.as_str(), "US");
+
+        let err = serde_json::from_str::<Plain<'_>>(r#""Portal 2""#).unwrap_err();
+        assert_eq!(err.to_string(), r#"invalid plain: "Portal 2""#);
+    }
+}
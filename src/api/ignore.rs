@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+
+use super::{endpoint::Endpoint, query, ApiError, AsyncClient, AsyncQuery, Client, Query};
+
+/// A query modifier which ignores the response data, only checking that the
+/// request succeeded.
+#[derive(Debug, Clone)]
+pub struct Ignore<E> {
+    endpoint: E,
+}
+
+/// Ignore the response data of an endpoint.
+pub fn ignore<E>(endpoint: E) -> Ignore<E> {
+    Ignore { endpoint }
+}
+
+impl<E, C> Query<(), C> for Ignore<E>
+where
+    E: Endpoint,
+    C: Client,
+{
+    fn query(&self, client: &C) -> Result<(), ApiError<C::Error>> {
+        let rsp = query::send(&self.endpoint, client)?;
+        if !rsp.status().is_success() {
+            query::response_value::<C::Error>(rsp)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<E, C> AsyncQuery<(), C> for Ignore<E>
+where
+    E: Endpoint + Sync,
+    C: AsyncClient + Sync,
+{
+    async fn query_async(&self, client: &C) -> Result<(), ApiError<C::Error>> {
+        let rsp = query::send_async(&self.endpoint, client).await?;
+        if !rsp.status().is_success() {
+            query::response_value::<C::Error>(rsp)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use http::StatusCode;
+
+    use super::*;
+    use crate::api::test_client::{Get, TestClient};
+
+    fn client() -> TestClient {
+        TestClient::new()
+            .respond_with("empty/", StatusCode::NO_CONTENT, "")
+            .respond("not_json/", "OK")
+            .respond_with(
+                "error/",
+                StatusCode::FORBIDDEN,
+                r#"{ "error": "invalid_token", "message": "Invalid token" }"#,
+            )
+            .respond_with("bad_error/", StatusCode::INTERNAL_SERVER_ERROR, "")
+    }
+
+    #[test]
+    fn accepts_any_successful_body() {
+        let client = client();
+        ignore(Get::new("empty/")).query(&client).unwrap();
+        ignore(Get::new("not_json/")).query(&client).unwrap();
+        block_on(ignore(Get::new("empty/")).query_async(&client)).unwrap();
+    }
+
+    #[test]
+    fn reports_failed_requests() {
+        let client = client();
+        let err = ignore(Get::new("error/")).query(&client).unwrap_err();
+        assert!(matches!(err, ApiError::ItadApi { msg } if msg == "Invalid token"));
+
+        let err = block_on(ignore(Get::new("bad_error/")).query_async(&client)).unwrap_err();
+        assert!(matches!(err, ApiError::Json { .. }));
+    }
+}
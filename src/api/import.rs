@@ -0,0 +1,423 @@
+//! Documents for the waitlist and collection imports.
+//!
+//! See: https://itad.docs.apiary.io/#reference/waitlist/import
+use std::borrow::Cow;
+
+use derive_builder::Builder;
+use serde::Serialize;
+
+use super::{Plain, ShopId};
+
+pub mod epic;
+pub mod gog;
+pub mod humble;
+mod library;
+pub mod steam;
+
+pub use library::{resolve, resolve_async, LibraryImport, LibraryItem};
+
+/// The import format version these documents follow.
+pub const IMPORT_VERSION: &str = "02";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
+pub struct WaitlistImportEntry<'a> {
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<Cow<'a, str>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plain: Option<Plain<'a>>,
+    #[builder(setter(name = "_game_id"), private, default)]
+    #[serde(rename = "gameid", skip_serializing_if = "Option::is_none")]
+    game_id: Option<(ShopId<'a>, Cow<'a, str>)>,
+}
+
+impl<'a> WaitlistImportEntry<'a> {
+    pub fn builder() -> WaitlistImportEntryBuilder<'a> {
+        WaitlistImportEntryBuilder::default()
+    }
+}
+
+impl<'a> WaitlistImportEntryBuilder<'a> {
+    /// The game's id at `shop`, such as `app/220` at `steam`.
+    pub fn game_id<S, T>(&mut self, shop: S, id: T) -> &mut Self
+    where
+        S: Into<ShopId<'a>>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.game_id = Some(Some((shop.into(), id.into())));
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        check_identified(&self.title, &self.plain, &self.game_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
+pub struct WaitlistImport<'a> {
+    #[builder(setter(skip), default = "IMPORT_VERSION")]
+    version: &'static str,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<Cow<'a, str>>,
+    #[builder(setter(name = "_data"), private)]
+    data: Vec<WaitlistImportEntry<'a>>,
+}
+
+impl<'a> WaitlistImport<'a> {
+    pub fn builder() -> WaitlistImportBuilder<'a> {
+        WaitlistImportBuilder::default()
+    }
+
+    pub fn entries(&self) -> &[WaitlistImportEntry<'a>] {
+        &self.data
+    }
+}
+
+impl<'a> WaitlistImportBuilder<'a> {
+    pub fn entry(&mut self, entry: WaitlistImportEntry<'a>) -> &mut Self {
+        self.data.get_or_insert_with(Vec::new).push(entry);
+        self
+    }
+
+    pub fn entries<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = WaitlistImportEntry<'a>>,
+    {
+        self.data.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        check_not_empty(&self.data)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct ImportCopy<'a> {
+    /// The shop the copy is owned at
+    #[serde(rename = "type")]
+    shop: ShopId<'a>,
+    /// The DRM the copy is activated with, if not the shop's own
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drm: Option<Cow<'a, str>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<Cow<'a, str>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owned: Option<bool>,
+    /// Unix timestamp of when the copy was added
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    added: Option<u64>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<Cow<'a, str>>,
+}
+
+impl<'a> ImportCopy<'a> {
+    pub fn builder() -> ImportCopyBuilder<'a> {
+        ImportCopyBuilder::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
+pub struct CollectionImportEntry<'a> {
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<Cow<'a, str>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plain: Option<Plain<'a>>,
+    #[builder(setter(name = "_game_id"), private, default)]
+    #[serde(rename = "gameid", skip_serializing_if = "Option::is_none")]
+    game_id: Option<(ShopId<'a>, Cow<'a, str>)>,
+    #[builder(setter(name = "_copies"), private, default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    copies: Vec<ImportCopy<'a>>,
+    #[builder(setter(name = "_groups"), private, default)]
+    #[serde(rename = "group", skip_serializing_if = "Vec::is_empty")]
+    groups: Vec<Cow<'a, str>>,
+}
+
+impl<'a> CollectionImportEntry<'a> {
+    pub fn builder() -> CollectionImportEntryBuilder<'a> {
+        CollectionImportEntryBuilder::default()
+    }
+}
+
+impl<'a> CollectionImportEntryBuilder<'a> {
+    /// The game's id at `shop`, such as `app/220` at `steam`.
+    pub fn game_id<S, T>(&mut self, shop: S, id: T) -> &mut Self
+    where
+        S: Into<ShopId<'a>>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.game_id = Some(Some((shop.into(), id.into())));
+        self
+    }
+
+    pub fn copy(&mut self, copy: ImportCopy<'a>) -> &mut Self {
+        self.copies.get_or_insert_with(Vec::new).push(copy);
+        self
+    }
+
+    pub fn copies<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = ImportCopy<'a>>,
+    {
+        self.copies.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+
+    pub fn group<T>(&mut self, group: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.groups.get_or_insert_with(Vec::new).push(group.into());
+        self
+    }
+
+    pub fn groups<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.groups
+            .get_or_insert_with(Vec::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        check_identified(&self.title, &self.plain, &self.game_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
+pub struct CollectionImport<'a> {
+    #[builder(setter(skip), default = "IMPORT_VERSION")]
+    version: &'static str,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<Cow<'a, str>>,
+    #[builder(setter(name = "_data"), private)]
+    data: Vec<CollectionImportEntry<'a>>,
+}
+
+impl<'a> CollectionImport<'a> {
+    pub fn builder() -> CollectionImportBuilder<'a> {
+        CollectionImportBuilder::default()
+    }
+
+    pub fn entries(&self) -> &[CollectionImportEntry<'a>] {
+        &self.data
+    }
+}
+
+impl<'a> CollectionImportBuilder<'a> {
+    pub fn entry(&mut self, entry: CollectionImportEntry<'a>) -> &mut Self {
+        self.data.get_or_insert_with(Vec::new).push(entry);
+        self
+    }
+
+    pub fn entries<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = CollectionImportEntry<'a>>,
+    {
+        self.data.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        check_not_empty(&self.data)
+    }
+}
+
+type GameIdField<'a> = Option<Option<(ShopId<'a>, Cow<'a, str>)>>;
+
+fn check_identified(
+    title: &Option<Option<Cow<'_, str>>>,
+    plain: &Option<Option<Plain<'_>>>,
+    game_id: &GameIdField<'_>,
+) -> Result<(), String> {
+    let title = title.as_ref().and_then(Option::as_ref);
+    if title.is_some_and(|title| title.trim().is_empty()) {
+        return Err("Title must not be blank".into());
+    }
+
+    if title.is_none()
+        && plain.iter().flatten().next().is_none()
+        && game_id.iter().flatten().next().is_none()
+    {
+        return Err("One of title, plain or game id must be set".into());
+    }
+
+    Ok(())
+}
+
+fn check_not_empty<T>(data: &Option<Vec<T>>) -> Result<(), String> {
+    if data.as_ref().is_none_or(Vec::is_empty) {
+        return Err("At least one entry is required".into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, to_value};
+
+    use super::*;
+
+    fn steam() -> ShopId<'static> {
+        ShopId::new("steam").unwrap()
+    }
+
+    #[test]
+    fn waitlist_import_format() {
+        let import = WaitlistImport::builder()
+            .source("test")
+            .entry(
+                WaitlistImportEntry::builder()
+                    .title("Portal")
+                    .plain(Plain::new("portal").unwrap())
+                    .game_id(steam(), "app/400")
+                    .build()
+                    .unwrap(),
+            )
+            .entry(
+                WaitlistImportEntry::builder()
+                    .title("Braid")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            to_value(&import).unwrap(),
+            json!({
+                "version": "02",
+                "source": "test",
+                "data": [
+                    { "title": "Portal", "plain": "portal", "gameid": ["steam", "app/400"] },
+                    { "title": "Braid" },
+                ],
+            })
+        );
+        assert_eq!(import.entries().len(), 2);
+    }
+
+    #[test]
+    fn collection_import_format() {
+        let copy = ImportCopy::builder()
+            .shop(steam())
+            .drm("steam")
+            .status("redeemed")
+            .owned(true)
+            .added(1_600_000_000u64)
+            .note("gift")
+            .build()
+            .unwrap();
+        let import = CollectionImport::builder()
+            .entry(
+                CollectionImportEntry::builder()
+                    .game_id(steam(), "app/400")
+                    .copy(copy)
+                    .copies(
+                        vec![ImportCopy::builder()
+                            .shop(ShopId::new("gog").unwrap())
+                            .build()
+                            .unwrap()]
+                        .into_iter(),
+                    )
+                    .group("puzzle")
+                    .groups(vec!["valve"].into_iter())
+                    .build()
+                    .unwrap(),
+            )
+            .entry(
+                CollectionImportEntry::builder()
+                    .plain(Plain::new("braid").unwrap())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            to_value(&import).unwrap(),
+            json!({
+                "version": "02",
+                "data": [
+                    {
+                        "gameid": ["steam", "app/400"],
+                        "copies": [
+                            {
+                                "type": "steam",
+                                "drm": "steam",
+                                "status": "redeemed",
+                                "owned": true,
+                                "added": 1_600_000_000u64,
+                                "note": "gift",
+                            },
+                            { "type": "gog" },
+                        ],
+                        "group": ["puzzle", "valve"],
+                    },
+                    { "plain": "braid" },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn entries_must_identify_a_game() {
+        let err = WaitlistImportEntry::builder().build().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("One of title, plain or game id must be set"));
+        assert!(CollectionImportEntry::builder()
+            .group("puzzle")
+            .build()
+            .is_err());
+        assert!(WaitlistImportEntry::builder()
+            .game_id(steam(), "app/400")
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn titles_must_not_be_blank() {
+        let err = WaitlistImportEntry::builder()
+            .title("  ")
+            .plain(Plain::new("portal").unwrap())
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("Title must not be blank"));
+        assert!(CollectionImportEntry::builder().title("").build().is_err());
+    }
+
+    #[test]
+    fn imports_need_entries() {
+        let err = WaitlistImport::builder().build().unwrap_err();
+        assert!(err.to_string().contains("At least one entry is required"));
+        assert!(WaitlistImport::builder()
+            .entries(Vec::new().into_iter())
+            .build()
+            .is_err());
+        assert!(CollectionImport::builder().source("test").build().is_err());
+    }
+
+    #[test]
+    fn copies_need_a_shop() {
+        assert!(ImportCopy::builder().owned(true).build().is_err());
+    }
+}
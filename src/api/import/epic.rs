@@ -0,0 +1,95 @@
+//! Parse an Epic Games Store library, as listed by `legendary list --json`.
+use serde::Deserialize;
+
+use super::LibraryItem;
+use crate::api::Shop;
+
+#[derive(Debug, Deserialize)]
+struct Game {
+    app_title: String,
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    #[serde(default)]
+    custom_attributes: CustomAttributes,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CustomAttributes {
+    /// The launcher the game is activated with, such as `Origin`
+    #[serde(rename = "ThirdPartyManagedApp")]
+    third_party_managed_app: Option<Attribute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Attribute {
+    value: String,
+}
+
+/// Parse the games from `legendary list --json`. Games activated with a third
+/// party launcher have it as their DRM.
+pub fn parse_library(json: &[u8]) -> Result<Vec<LibraryItem>, serde_json::Error> {
+    let games: Vec<Game> = serde_json::from_slice(json)?;
+    Ok(games
+        .into_iter()
+        .map(|game| LibraryItem {
+            title: game.app_title,
+            shop: Shop::Epic,
+            drm: game
+                .metadata
+                .custom_attributes
+                .third_party_managed_app
+                .map(|app| app.value.to_lowercase()),
+            lookup: None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn library() {
+        let json = br#"[
+            {
+                "app_name": "Fortnite",
+                "app_title": "Fortnite",
+                "metadata": { "customAttributes": {} }
+            },
+            {
+                "app_name": "Quail",
+                "app_title": "Battlefield 1",
+                "metadata": {
+                    "customAttributes": {
+                        "ThirdPartyManagedApp": { "type": "STRING", "value": "Origin" }
+                    }
+                }
+            },
+            { "app_name": "Sugar", "app_title": "Celeste" }
+        ]"#;
+        let item = |title: &str, drm: Option<&str>| LibraryItem {
+            title: title.into(),
+            shop: Shop::Epic,
+            drm: drm.map(String::from),
+            lookup: None,
+        };
+        assert_eq!(
+            parse_library(json).unwrap(),
+            vec![
+                item("Fortnite", None),
+                item("Battlefield 1", Some("origin")),
+                item("Celeste", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn requires_titles() {
+        assert!(parse_library(br#"[{ "app_name": "Fortnite" }]"#).is_err());
+    }
+}
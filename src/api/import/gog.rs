@@ -0,0 +1,167 @@
+//! Parse a GOG Galaxy library.
+//!
+//! Galaxy keeps its library in `galaxy-2.0.db`. Export the titles with:
+//!
+//! ```sh
+//! sqlite3 -json galaxy-2.0.db "SELECT releaseKey, value FROM GamePieces \
+//!     JOIN GamePieceTypes ON GamePieces.gamePieceTypeId = GamePieceTypes.id \
+//!     WHERE GamePieceTypes.type = 'title'"
+//! ```
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use super::LibraryItem;
+use crate::api::Shop;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GamePiece {
+    release_key: String,
+    title: Option<String>,
+    /// The piece's JSON value, as stored in the database
+    value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TitleValue {
+    title: Option<String>,
+}
+
+/// The shop a Galaxy platform id stands for.
+fn platform_shop(platform: &str) -> Option<Shop> {
+    Some(match platform {
+        "amazon" => Shop::Amazon,
+        "battlenet" => Shop::BattleNet,
+        "epic" => Shop::Epic,
+        "gog" => Shop::Gog,
+        "humble" => Shop::HumbleStore,
+        "itch" => Shop::ItchIo,
+        "origin" => Shop::Origin,
+        "steam" => Shop::Steam,
+        "uplay" => Shop::Uplay,
+        "xboxone" => Shop::Microsoft,
+        _ => return None,
+    })
+}
+
+/// The id to look up a game's plain by. Only Steam app ids have a
+/// documented form in ITAD's id lookups (`app/<id>`). Galaxy's ids for other
+/// platforms, GOG product ids included, are not in a form those lookups are
+/// documented to accept, so those games are matched by title.
+fn id_lookup(shop: &Shop, id: &str) -> Option<(Shop, String)> {
+    match shop {
+        Shop::Steam => Some((Shop::Steam, format!("app/{}", id))),
+        _ => None,
+    }
+}
+
+fn item(release_key: &str, title: String) -> Option<LibraryItem> {
+    let (platform, id) = release_key.split_once('_')?;
+    let shop = platform_shop(platform)?;
+    let lookup = id_lookup(&shop, id);
+    Some(LibraryItem {
+        title,
+        shop,
+        drm: None,
+        lookup,
+    })
+}
+
+/// Parse the games from an export of Galaxy's `GamePieces` titles, as rows of
+/// `releaseKey` with either `title` or the piece's JSON `value`.
+///
+/// Games from platforms without a shop at ITAD, and rows whose `value` is not
+/// a title, are skipped. Only Steam games carry an id lookup; games from GOG
+/// and other platforms are matched by title.
+pub fn parse_galaxy_export(json: &[u8]) -> Result<Vec<LibraryItem>, serde_json::Error> {
+    let pieces: Vec<GamePiece> = serde_json::from_slice(json)?;
+    let mut titles = BTreeMap::new();
+    for piece in pieces {
+        let title = match (piece.title, piece.value) {
+            (Some(title), _) => Some(title),
+            (None, Some(value)) => serde_json::from_str::<TitleValue>(&value)
+                .ok()
+                .and_then(|value| value.title),
+            (None, None) => None,
+        };
+        if let Some(title) = title {
+            titles.insert(piece.release_key, title);
+        }
+    }
+
+    Ok(titles
+        .into_iter()
+        .filter_map(|(release_key, title)| item(&release_key, title))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(title: &str, shop: Shop, lookup: Option<&str>) -> LibraryItem {
+        LibraryItem {
+            title: title.into(),
+            shop,
+            drm: None,
+            lookup: lookup.map(|id| (Shop::Steam, id.into())),
+        }
+    }
+
+    #[test]
+    fn titles_and_values() {
+        let json = br#"[
+            { "releaseKey": "steam_220", "value": "{\"title\":\"Half-Life 2\"}" },
+            { "releaseKey": "gog_1207658924", "title": "The Witcher" },
+            { "releaseKey": "epic_Fortnite", "value": "{\"title\":\"Fortnite\"}" }
+        ]"#;
+        assert_eq!(
+            parse_galaxy_export(json).unwrap(),
+            vec![
+                item("Fortnite", Shop::Epic, None),
+                item("The Witcher", Shop::Gog, None),
+                item("Half-Life 2", Shop::Steam, Some("app/220")),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_malformed_values() {
+        let json = br#"[
+            { "releaseKey": "steam_220", "value": "not json" },
+            { "releaseKey": "steam_400", "value": "{\"title\":null}" },
+            { "releaseKey": "steam_620" },
+            { "releaseKey": "gog_1207658924", "title": "The Witcher" }
+        ]"#;
+        assert_eq!(
+            parse_galaxy_export(json).unwrap(),
+            vec![item("The Witcher", Shop::Gog, None)]
+        );
+    }
+
+    #[test]
+    fn only_steam_games_have_id_lookups() {
+        assert_eq!(
+            id_lookup(&Shop::Steam, "220"),
+            Some((Shop::Steam, "app/220".into()))
+        );
+        assert_eq!(id_lookup(&Shop::Gog, "1207658924"), None);
+        assert_eq!(id_lookup(&Shop::Epic, "Fortnite"), None);
+    }
+
+    #[test]
+    fn skips_unknown_platforms() {
+        let json = br#"[
+            { "releaseKey": "generic_1", "title": "Added By Hand" },
+            { "releaseKey": "psn_CUSA00001", "title": "On A Console" },
+            { "releaseKey": "noplatform", "title": "Without An Id" }
+        ]"#;
+        assert_eq!(parse_galaxy_export(json).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert!(parse_galaxy_export(br#"{ "releaseKey": "steam_220" }"#).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! Parse Humble Bundle orders, as returned by `/api/v1/order/{gamekey}`.
+use serde::Deserialize;
+
+use super::LibraryItem;
+use crate::api::Shop;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Orders {
+    Many(Vec<Order>),
+    One(Order),
+}
+
+#[derive(Debug, Deserialize)]
+struct Order {
+    #[serde(default)]
+    subproducts: Vec<Subproduct>,
+    #[serde(default)]
+    tpkd_dict: KeyDict,
+}
+
+#[derive(Debug, Deserialize)]
+struct Subproduct {
+    human_name: String,
+    #[serde(default)]
+    downloads: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct KeyDict {
+    #[serde(default)]
+    all_tpks: Vec<Key>,
+}
+
+/// A third party key
+#[derive(Debug, Deserialize)]
+struct Key {
+    human_name: String,
+    key_type: String,
+    steam_app_id: Option<u32>,
+}
+
+/// Parse the games from one order or a list of orders. Downloads are DRM-free
+/// copies and keys are copies with the key's platform as their DRM.
+pub fn parse_orders(json: &[u8]) -> Result<Vec<LibraryItem>, serde_json::Error> {
+    let orders = match serde_json::from_slice(json)? {
+        Orders::Many(orders) => orders,
+        Orders::One(order) => vec![order],
+    };
+
+    let mut items = Vec::new();
+    for order in orders {
+        items.extend(
+            order
+                .subproducts
+                .into_iter()
+                .filter(|subproduct| !subproduct.downloads.is_empty())
+                .map(|subproduct| LibraryItem {
+                    title: subproduct.human_name,
+                    shop: Shop::HumbleStore,
+                    drm: Some("drm-free".into()),
+                    lookup: None,
+                }),
+        );
+        items.extend(order.tpkd_dict.all_tpks.into_iter().map(|key| {
+            LibraryItem {
+                title: key.human_name,
+                shop: Shop::HumbleStore,
+                lookup: key
+                    .steam_app_id
+                    .map(|appid| (Shop::Steam, format!("app/{}", appid))),
+                drm: Some(key.key_type),
+            }
+        }));
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER: &str = r#"{
+        "gamekey": "abc",
+        "subproducts": [
+            { "human_name": "Braid", "downloads": [{ "platform": "windows" }] },
+            { "human_name": "Soundtrack Only", "downloads": [] }
+        ],
+        "tpkd_dict": {
+            "all_tpks": [
+                { "human_name": "Portal", "key_type": "steam", "steam_app_id": 400 },
+                { "human_name": "Trine", "key_type": "gog", "steam_app_id": null }
+            ]
+        }
+    }"#;
+
+    fn item(title: &str, drm: &str, lookup: Option<&str>) -> LibraryItem {
+        LibraryItem {
+            title: title.into(),
+            shop: Shop::HumbleStore,
+            drm: Some(drm.into()),
+            lookup: lookup.map(|id| (Shop::Steam, id.into())),
+        }
+    }
+
+    #[test]
+    fn one_order() {
+        assert_eq!(
+            parse_orders(ORDER.as_bytes()).unwrap(),
+            vec![
+                item("Braid", "drm-free", None),
+                item("Portal", "steam", Some("app/400")),
+                item("Trine", "gog", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn many_orders() {
+        let json = format!(r#"[{}, {{ "gamekey": "empty" }}, {}]"#, ORDER, ORDER);
+        let items = parse_orders(json.as_bytes()).unwrap();
+        assert_eq!(items.len(), 6);
+        assert_eq!(items[..3], items[3..]);
+    }
+}
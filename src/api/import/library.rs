@@ -0,0 +1,302 @@
+//! Match library items from any store to plains.
+use std::{borrow::Cow, collections::BTreeMap};
+
+use futures::{StreamExt, TryStreamExt};
+
+use super::{CollectionImport, CollectionImportBuilderError, CollectionImportEntry, ImportCopy};
+use crate::api::{
+    game::{IdPlainMap, MapType, MultiplePlainsById},
+    ApiError, AsyncClient, AsyncQuery, Client, GameRef, Plain, Query, Shop,
+};
+
+/// Lists with more ids than this are matched against the shop's full id map.
+const FULL_MAP_THRESHOLD: usize = 500;
+/// The most ids sent in one `MultiplePlainsById` query.
+const IDS_PER_QUERY: usize = 100;
+/// The most title lookups in flight at once.
+const TITLE_CONCURRENCY: usize = 8;
+
+pub(super) type Plains = BTreeMap<String, Option<Plain<'static>>>;
+
+enum Lookup<'a> {
+    Map(IdPlainMap<'a>),
+    Ids(Vec<MultiplePlainsById<'a>>),
+}
+
+fn lookup(shop: &Shop, ids: &[String]) -> Lookup<'static> {
+    if ids.len() > FULL_MAP_THRESHOLD {
+        return Lookup::Map(
+            IdPlainMap::builder()
+                .shop(shop.clone())
+                .type_field(MapType::IdToPlain)
+                .build()
+                .expect("shop and type are set"),
+        );
+    }
+
+    Lookup::Ids(
+        ids.chunks(IDS_PER_QUERY)
+            .map(|chunk| {
+                MultiplePlainsById::builder()
+                    .shop(shop.clone())
+                    .ids(chunk.iter().map(|id| Cow::Owned(id.clone())))
+                    .build()
+                    .expect("shop and ids are set")
+            })
+            .collect(),
+    )
+}
+
+/// Look up the plains of game ids at `shop`.
+pub(super) fn plains_by_id<C>(
+    client: &C,
+    shop: &Shop,
+    ids: &[String],
+) -> Result<Plains, ApiError<C::Error>>
+where
+    C: Client,
+{
+    match lookup(shop, ids) {
+        Lookup::Map(endpoint) => endpoint.query(client),
+        Lookup::Ids(endpoints) => {
+            let mut plains = Plains::new();
+            for endpoint in endpoints {
+                let chunk: Plains = endpoint.query(client)?;
+                plains.extend(chunk);
+            }
+            Ok(plains)
+        }
+    }
+}
+
+/// Look up the plains of game ids at `shop` asynchronously.
+pub(super) async fn plains_by_id_async<C>(
+    client: &C,
+    shop: &Shop,
+    ids: &[String],
+) -> Result<Plains, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    match lookup(shop, ids) {
+        Lookup::Map(endpoint) => endpoint.query_async(client).await,
+        Lookup::Ids(endpoints) => {
+            let chunks: Vec<Plains> = futures::future::try_join_all(
+                endpoints
+                    .iter()
+                    .map(|endpoint| endpoint.query_async(client)),
+            )
+            .await?;
+            Ok(chunks.into_iter().flatten().collect())
+        }
+    }
+}
+
+/// A game in a store library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryItem {
+    pub title: String,
+    /// The shop the copy was bought at
+    pub shop: Shop,
+    /// The DRM the copy is activated with, if not the shop's own
+    pub drm: Option<String>,
+    /// A shop and the game's id there, to look up the plain by
+    pub lookup: Option<(Shop, String)>,
+}
+
+/// Library items split by whether ITAD knows them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LibraryImport {
+    pub matched: Vec<(LibraryItem, Plain<'static>)>,
+    pub unmatched: Vec<LibraryItem>,
+}
+
+impl LibraryImport {
+    /// A collection import of the matched items, with one entry per plain
+    /// and a copy for each item.
+    pub fn collection<'a>(
+        &'a self,
+        source: &'a str,
+    ) -> Result<CollectionImport<'a>, CollectionImportBuilderError> {
+        let mut entries: BTreeMap<&Plain<'static>, Vec<&LibraryItem>> = BTreeMap::new();
+        for (item, plain) in &self.matched {
+            entries.entry(plain).or_default().push(item);
+        }
+
+        CollectionImport::builder()
+            .source(source)
+            .entries(entries.into_iter().map(|(plain, items)| {
+                CollectionImportEntry::builder()
+                    .title(items[0].title.as_str())
+                    .plain(plain.clone())
+                    .copies(items.into_iter().map(|item| {
+                        let mut copy = ImportCopy::builder();
+                        if let Some(drm) = &item.drm {
+                            copy.drm(drm.as_str());
+                        }
+                        copy.shop(&item.shop)
+                            .owned(true)
+                            .build()
+                            .expect("shop is set")
+                    }))
+                    .build()
+                    .expect("title and plain are set")
+            }))
+            .build()
+    }
+}
+
+fn id_lookups(items: &[LibraryItem]) -> BTreeMap<&Shop, Vec<String>> {
+    let mut ids: BTreeMap<&Shop, Vec<String>> = BTreeMap::new();
+    for (shop, id) in items.iter().filter_map(|item| item.lookup.as_ref()) {
+        ids.entry(shop).or_default().push(id.clone());
+    }
+    ids
+}
+
+fn matched_by_id(item: &LibraryItem, plains: &BTreeMap<Shop, Plains>) -> Option<Plain<'static>> {
+    let (shop, id) = item.lookup.as_ref()?;
+    plains.get(shop)?.get(id).cloned().flatten()
+}
+
+fn title_ref(item: &LibraryItem) -> GameRef<'_> {
+    GameRef::Title(item.title.as_str().into())
+}
+
+/// Match library items to plains, by their shop ids where they have one and
+/// by title otherwise. Items no game matches are left unmatched; any other
+/// error fails the whole import.
+pub fn resolve<C>(client: &C, items: Vec<LibraryItem>) -> Result<LibraryImport, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let mut plains = BTreeMap::new();
+    for (shop, ids) in id_lookups(&items) {
+        plains.insert(shop.clone(), plains_by_id(client, shop, &ids)?);
+    }
+
+    let mut import = LibraryImport::default();
+    for item in items {
+        let plain = match matched_by_id(&item, &plains) {
+            Some(plain) => Some(plain),
+            None => title_ref(&item).resolve_plain(client)?,
+        };
+        match plain {
+            Some(plain) => import.matched.push((item, plain)),
+            None => import.unmatched.push(item),
+        }
+    }
+    Ok(import)
+}
+
+/// Match library items to plains asynchronously.
+pub async fn resolve_async<C>(
+    client: &C,
+    items: Vec<LibraryItem>,
+) -> Result<LibraryImport, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let mut plains = BTreeMap::new();
+    for (shop, ids) in id_lookups(&items) {
+        plains.insert(shop.clone(), plains_by_id_async(client, shop, &ids).await?);
+    }
+
+    let plains = &plains;
+    let resolved: Vec<(LibraryItem, Option<Plain<'static>>)> = futures::stream::iter(items)
+        .map(|item| async move {
+            let plain = match matched_by_id(&item, plains) {
+                Some(plain) => Some(plain),
+                None => title_ref(&item).resolve_plain_async(client).await?,
+            };
+            Ok::<_, ApiError<C::Error>>((item, plain))
+        })
+        .buffered(TITLE_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    let mut import = LibraryImport::default();
+    for (item, plain) in resolved {
+        match plain {
+            Some(plain) => import.matched.push((item, plain)),
+            None => import.unmatched.push(item),
+        }
+    }
+    Ok(import)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use http::StatusCode;
+
+    use super::*;
+    use crate::api::test_client::TestClient;
+
+    fn item(title: &str, lookup: Option<&str>) -> LibraryItem {
+        LibraryItem {
+            title: title.into(),
+            shop: Shop::Steam,
+            drm: None,
+            lookup: lookup.map(|id| (Shop::Steam, id.into())),
+        }
+    }
+
+    fn client(title_lookup: &str) -> TestClient {
+        TestClient::new()
+            .respond(
+                "v01/game/plain/id/",
+                r#"{ "data": { "app/220": "halflifeii", "app/400": null } }"#,
+            )
+            .respond("v02/game/plain/", title_lookup.to_owned())
+    }
+
+    #[test]
+    fn matches_by_id_then_title() {
+        let client = client(r#"{ "data": { "found": true, "plain": "portal" } }"#);
+        let items = vec![
+            item("Half-Life 2", Some("app/220")),
+            item("Portal", Some("app/400")),
+        ];
+        let import = resolve(&client, items.clone()).unwrap();
+        assert_eq!(
+            import.matched,
+            vec![
+                (items[0].clone(), Plain::new("halflifeii").unwrap()),
+                (items[1].clone(), Plain::new("portal").unwrap()),
+            ]
+        );
+        assert!(import.unmatched.is_empty());
+        assert_eq!(block_on(resolve_async(&client, items)).unwrap(), import);
+    }
+
+    #[test]
+    fn leaves_unknown_games_unmatched() {
+        let client = client(r#"{ "data": { "found": false } }"#);
+        let items = vec![item("Half-Life 2", Some("app/220")), item("Portal 3", None)];
+        let import = resolve(&client, items.clone()).unwrap();
+        assert_eq!(import.matched.len(), 1);
+        assert_eq!(import.unmatched, vec![items[1].clone()]);
+        assert_eq!(block_on(resolve_async(&client, items)).unwrap(), import);
+    }
+
+    #[test]
+    fn fails_on_title_lookup_errors() {
+        let client = TestClient::new().respond_with(
+            "v02/game/plain/",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            r#"{ "error": "oops" }"#,
+        );
+        let items = vec![item("Portal", None)];
+        assert!(resolve(&client, items.clone()).is_err());
+        assert!(block_on(resolve_async(&client, items)).is_err());
+    }
+
+    #[test]
+    fn fails_on_id_lookup_errors() {
+        let client = TestClient::new();
+        let items = vec![item("Half-Life 2", Some("app/220"))];
+        assert!(resolve(&client, items.clone()).is_err());
+        assert!(block_on(resolve_async(&client, items)).is_err());
+    }
+}
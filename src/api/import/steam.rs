@@ -0,0 +1,321 @@
+//! Import a Steam library or wishlist.
+//!
+//! Apps are parsed from a `GetOwnedGames` response or a wishlist export, then
+//! matched to plains with `MultiplePlainsById`, or `IdPlainMap` for large
+//! libraries.
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use super::{
+    library::{plains_by_id, plains_by_id_async, Plains},
+    CollectionImport, CollectionImportBuilderError, CollectionImportEntry, ImportCopy,
+    WaitlistImport, WaitlistImportBuilderError, WaitlistImportEntry,
+};
+use crate::api::{ApiError, AsyncClient, Client, Plain, Shop};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteamApp {
+    pub appid: u32,
+    pub name: Option<String>,
+}
+
+impl SteamApp {
+    /// The app's ITAD game id at Steam, such as `app/220`.
+    pub fn game_id(&self) -> String {
+        format!("app/{}", self.appid)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnedGames {
+    response: OwnedGamesResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnedGamesResponse {
+    #[serde(default)]
+    games: Vec<OwnedGame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnedGame {
+    appid: u32,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Wishlist {
+    /// `IWishlistService/GetWishlist`
+    Service { response: WishlistResponse },
+    /// The store's `wishlistdata`, keyed by app id
+    Store(BTreeMap<String, WishlistData>),
+}
+
+#[derive(Debug, Deserialize)]
+struct WishlistResponse {
+    #[serde(default)]
+    items: Vec<WishlistItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WishlistItem {
+    appid: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct WishlistData {
+    name: Option<String>,
+}
+
+/// Parse the apps from an `IPlayerService/GetOwnedGames` response.
+pub fn parse_owned_games(json: &[u8]) -> Result<Vec<SteamApp>, serde_json::Error> {
+    let owned: OwnedGames = serde_json::from_slice(json)?;
+    Ok(owned
+        .response
+        .games
+        .into_iter()
+        .map(|game| SteamApp {
+            appid: game.appid,
+            name: game.name,
+        })
+        .collect())
+}
+
+/// Parse the apps from a wishlist, either an `IWishlistService/GetWishlist`
+/// response or the store's `wishlistdata`.
+pub fn parse_wishlist(json: &[u8]) -> Result<Vec<SteamApp>, serde_json::Error> {
+    Ok(match serde_json::from_slice(json)? {
+        Wishlist::Service { response } => response
+            .items
+            .into_iter()
+            .map(|item| SteamApp {
+                appid: item.appid,
+                name: None,
+            })
+            .collect(),
+        Wishlist::Store(apps) => apps
+            .into_iter()
+            .map(|(appid, data)| {
+                Ok(SteamApp {
+                    appid: appid.parse().map_err(serde::de::Error::custom)?,
+                    name: data.name,
+                })
+            })
+            .collect::<Result<_, serde_json::Error>>()?,
+    })
+}
+
+/// Steam apps split by whether ITAD knows them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SteamImport {
+    pub matched: Vec<(SteamApp, Plain<'static>)>,
+    pub unmatched: Vec<SteamApp>,
+}
+
+impl SteamImport {
+    fn new(apps: Vec<SteamApp>, plains: Plains) -> Self {
+        let mut import = Self::default();
+        for app in apps {
+            match plains.get(&app.game_id()).cloned().flatten() {
+                Some(plain) => import.matched.push((app, plain)),
+                None => import.unmatched.push(app),
+            }
+        }
+        import
+    }
+
+    /// A collection import of the matched apps, each with a Steam copy.
+    pub fn collection(&self) -> Result<CollectionImport<'_>, CollectionImportBuilderError> {
+        CollectionImport::builder()
+            .source("steam")
+            .entries(self.matched.iter().map(|(app, plain)| {
+                let mut entry = CollectionImportEntry::builder();
+                if let Some(name) = &app.name {
+                    entry.title(name.as_str());
+                }
+                entry
+                    .plain(plain.clone())
+                    .game_id(Shop::Steam, app.game_id())
+                    .copy(
+                        ImportCopy::builder()
+                            .shop(Shop::Steam)
+                            .owned(true)
+                            .build()
+                            .expect("shop is set"),
+                    )
+                    .build()
+                    .expect("plain is set")
+            }))
+            .build()
+    }
+
+    /// A waitlist import of the matched apps.
+    pub fn waitlist(&self) -> Result<WaitlistImport<'_>, WaitlistImportBuilderError> {
+        WaitlistImport::builder()
+            .source("steam")
+            .entries(self.matched.iter().map(|(app, plain)| {
+                let mut entry = WaitlistImportEntry::builder();
+                if let Some(name) = &app.name {
+                    entry.title(name.as_str());
+                }
+                entry
+                    .plain(plain.clone())
+                    .game_id(Shop::Steam, app.game_id())
+                    .build()
+                    .expect("plain is set")
+            }))
+            .build()
+    }
+}
+
+/// Match Steam apps to plains.
+pub fn match_apps<C>(client: &C, apps: Vec<SteamApp>) -> Result<SteamImport, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let ids: Vec<String> = apps.iter().map(SteamApp::game_id).collect();
+    let plains = plains_by_id(client, &Shop::Steam, &ids)?;
+    Ok(SteamImport::new(apps, plains))
+}
+
+/// Match Steam apps to plains asynchronously.
+pub async fn match_apps_async<C>(
+    client: &C,
+    apps: Vec<SteamApp>,
+) -> Result<SteamImport, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let ids: Vec<String> = apps.iter().map(SteamApp::game_id).collect();
+    let plains = plains_by_id_async(client, &Shop::Steam, &ids).await?;
+    Ok(SteamImport::new(apps, plains))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn app(appid: u32, name: Option<&str>) -> SteamApp {
+        SteamApp {
+            appid,
+            name: name.map(String::from),
+        }
+    }
+
+    #[test]
+    fn owned_games() {
+        let json = br#"{
+            "response": {
+                "game_count": 2,
+                "games": [
+                    { "appid": 220, "name": "Half-Life 2", "playtime_forever": 1207 },
+                    { "appid": 400, "playtime_forever": 0 }
+                ]
+            }
+        }"#;
+        assert_eq!(
+            parse_owned_games(json).unwrap(),
+            vec![app(220, Some("Half-Life 2")), app(400, None)]
+        );
+    }
+
+    #[test]
+    fn owned_games_of_private_profile() {
+        assert_eq!(parse_owned_games(br#"{ "response": {} }"#).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn wishlist_from_service() {
+        let json = br#"{
+            "response": {
+                "items": [
+                    { "appid": 1145360, "priority": 1, "date_added": 1600000000 },
+                    { "appid": 367520, "priority": 2 }
+                ]
+            }
+        }"#;
+        assert_eq!(
+            parse_wishlist(json).unwrap(),
+            vec![app(1145360, None), app(367520, None)]
+        );
+    }
+
+    #[test]
+    fn wishlist_from_store() {
+        let json = br#"{
+            "367520": { "name": "Hollow Knight", "added": 1500000000, "priority": 2 },
+            "1145360": { "name": "Hades", "added": 1600000000, "priority": 1 }
+        }"#;
+        let mut apps = parse_wishlist(json).unwrap();
+        apps.sort_by_key(|app| app.appid);
+        assert_eq!(
+            apps,
+            vec![
+                app(367520, Some("Hollow Knight")),
+                app(1145360, Some("Hades"))
+            ]
+        );
+    }
+
+    #[test]
+    fn wishlist_rejects_bad_app_ids() {
+        assert!(parse_wishlist(br#"{ "hades": { "name": "Hades" } }"#).is_err());
+    }
+
+    #[test]
+    fn import_splits_matched_and_unmatched() {
+        let mut plains = Plains::new();
+        plains.insert("app/220".into(), Some(Plain::new("halflifeii").unwrap()));
+        plains.insert("app/400".into(), None);
+        let apps = vec![
+            app(220, Some("Half-Life 2")),
+            app(400, Some("Portal")),
+            app(620, None),
+        ];
+        let import = SteamImport::new(apps, plains);
+        assert_eq!(
+            import.matched,
+            vec![(
+                app(220, Some("Half-Life 2")),
+                Plain::new("halflifeii").unwrap()
+            )]
+        );
+        assert_eq!(
+            import.unmatched,
+            vec![app(400, Some("Portal")), app(620, None)]
+        );
+    }
+
+    #[test]
+    fn import_matches_repeated_apps() {
+        let mut plains = Plains::new();
+        plains.insert("app/220".into(), Some(Plain::new("halflifeii").unwrap()));
+        let import = SteamImport::new(vec![app(220, None), app(220, None)], plains);
+        assert_eq!(import.matched.len(), 2);
+        assert!(import.unmatched.is_empty());
+    }
+
+    #[test]
+    fn collection_document() {
+        let mut plains = Plains::new();
+        plains.insert("app/220".into(), Some(Plain::new("halflifeii").unwrap()));
+        let import = SteamImport::new(vec![app(220, Some("Half-Life 2"))], plains);
+        assert_eq!(
+            serde_json::to_value(import.collection().unwrap()).unwrap(),
+            json!({
+                "version": "02",
+                "source": "steam",
+                "data": [{
+                    "title": "Half-Life 2",
+                    "plain": "halflifeii",
+                    "gameid": ["steam", "app/220"],
+                    "copies": [{ "type": "steam", "owned": true }],
+                }],
+            })
+        );
+    }
+}
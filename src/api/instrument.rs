@@ -0,0 +1,124 @@
+//! Observability hooks for queries. Everything here compiles down to nothing
+//! unless the `tracing` or `metrics` features are enabled.
+
+use std::{borrow::Cow, future::Future, time::Instant};
+
+use bytes::Bytes;
+use http::{Method, Response};
+use url::Url;
+
+use super::{client::Retries, endpoint::Endpoint};
+
+pub(crate) struct QueryObserver {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    endpoint: Cow<'static, str>,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    method: Method,
+    start: Instant,
+}
+
+impl QueryObserver {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn new<E>(endpoint: &E, url: &Url) -> Self
+    where
+        E: Endpoint + ?Sized,
+    {
+        let path = endpoint.endpoint();
+        let method = endpoint.method();
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "itad_query",
+            endpoint = %path,
+            method = %method,
+            url = %super::query::redact_url(url),
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+            retries = tracing::field::Empty,
+        );
+        Self {
+            #[cfg(feature = "tracing")]
+            span,
+            endpoint: path,
+            method,
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn in_scope<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        #[cfg(feature = "tracing")]
+        let _guard = self.span.enter();
+        f()
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn instrument<F>(&self, future: F) -> tracing::instrument::Instrumented<F>
+    where
+        F: Future,
+    {
+        tracing::Instrument::instrument(future, self.span.clone())
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn instrument<F>(&self, future: F) -> F
+    where
+        F: Future,
+    {
+        future
+    }
+
+    /// Record the outcome of the request. `response` is `None` if no response
+    /// was received. Retries are read from the response's [`Retries`]
+    /// extension, and are 0 if it is missing or there is no response.
+    #[cfg_attr(
+        not(any(feature = "tracing", feature = "metrics")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn finish(&self, response: Option<&Response<Bytes>>) {
+        let latency = self.start.elapsed();
+        let status = response.map(Response::status);
+        let retries = response.map_or(0, Retries::of);
+
+        #[cfg(feature = "tracing")]
+        {
+            match status {
+                Some(status) => self.span.record("status", status.as_u16()),
+                None => self.span.record("status", "error"),
+            };
+            self.span.record("latency_ms", latency.as_millis() as u64);
+            self.span.record("retries", retries);
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let status = status
+                .map(|status| status.as_str().to_owned())
+                .unwrap_or_else(|| "error".into());
+            let endpoint = self.endpoint.to_string();
+            let method = self.method.to_string();
+            metrics::counter!(
+                "itad_api_requests_total",
+                "endpoint" => endpoint.clone(),
+                "method" => method.clone(),
+                "status" => status,
+            )
+            .increment(1);
+            metrics::histogram!(
+                "itad_api_request_duration_seconds",
+                "endpoint" => endpoint.clone(),
+                "method" => method.clone(),
+            )
+            .record(latency.as_secs_f64());
+            metrics::histogram!(
+                "itad_api_request_retries",
+                "endpoint" => endpoint,
+                "method" => method,
+            )
+            .record(f64::from(retries));
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use super::{endpoint::Endpoint, query, ApiError, AsyncClient, AsyncQuery, Client, Query};
+
+/// A response with both the `data` and `.meta` members of the envelope.
+#[derive(Default, Debug, Clone, Deserialize, PartialEq)]
+pub struct Response<T, M> {
+    pub data: T,
+    #[serde(rename = ".meta", default)]
+    pub meta: M,
+}
+
+/// An endpoint whose responses carry typed metadata.
+///
+/// Only `DealsList`, `Prices`, `HistoricalLow`, `StoreLow` and `Overview`
+/// have a typed `.meta`; the other endpoints' metadata can be read through
+/// [`with_meta`] into a `serde_json::Value` or a type of the caller's.
+pub trait EndpointMeta: Endpoint {
+    type Meta: DeserializeOwned + Default;
+}
+
+/// A query modifier which returns the `.meta` member of the response envelope
+/// alongside the `data` member. Endpoints without an envelope, such as the v2
+/// ones, return their whole response as the data and default metadata.
+#[derive(Debug, Clone)]
+pub struct WithMeta<E> {
+    endpoint: E,
+}
+
+/// Return both the data and the metadata of an endpoint's response.
+pub fn with_meta<E>(endpoint: E) -> WithMeta<E> {
+    WithMeta { endpoint }
+}
+
+fn decode<T, M, E>(
+    rsp: http::Response<bytes::Bytes>,
+    enveloped: bool,
+) -> Result<Response<T, M>, ApiError<E>>
+where
+    T: DeserializeOwned,
+    M: DeserializeOwned + Default,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if !enveloped {
+        return query::decode_response(rsp, false).map(|data| Response {
+            data,
+            meta: M::default(),
+        });
+    }
+    serde_json::from_value(query::response_value(rsp)?)
+        .map_err(ApiError::data_type::<Response<T, M>>)
+}
+
+impl<E, T, M, C> Query<Response<T, M>, C> for WithMeta<E>
+where
+    E: Endpoint,
+    T: DeserializeOwned,
+    M: DeserializeOwned + Default,
+    C: Client,
+{
+    fn query(&self, client: &C) -> Result<Response<T, M>, ApiError<C::Error>> {
+        decode(
+            query::send(&self.endpoint, client)?,
+            self.endpoint.is_enveloped(),
+        )
+    }
+}
+
+#[async_trait]
+impl<E, T, M, C> AsyncQuery<Response<T, M>, C> for WithMeta<E>
+where
+    E: Endpoint + Sync,
+    T: DeserializeOwned + 'static,
+    M: DeserializeOwned + Default + 'static,
+    C: AsyncClient + Sync,
+{
+    async fn query_async(&self, client: &C) -> Result<Response<T, M>, ApiError<C::Error>> {
+        decode(
+            query::send_async(&self.endpoint, client).await?,
+            self.endpoint.is_enveloped(),
+        )
+    }
+}
+
+/// Query made to a client, returning the endpoint's typed metadata.
+pub trait QueryMeta<T, C>: EndpointMeta
+where
+    C: Client,
+{
+    fn query_meta(&self, client: &C) -> Result<Response<T, Self::Meta>, ApiError<C::Error>>;
+}
+
+/// Asynchronous query made to a client, returning the endpoint's typed
+/// metadata.
+#[async_trait]
+pub trait AsyncQueryMeta<T, C>: EndpointMeta
+where
+    C: AsyncClient,
+{
+    async fn query_meta_async(
+        &self,
+        client: &C,
+    ) -> Result<Response<T, Self::Meta>, ApiError<C::Error>>;
+}
+
+impl<E, T, C> QueryMeta<T, C> for E
+where
+    E: EndpointMeta,
+    T: DeserializeOwned,
+    C: Client,
+{
+    fn query_meta(&self, client: &C) -> Result<Response<T, E::Meta>, ApiError<C::Error>> {
+        decode(query::send(self, client)?, self.is_enveloped())
+    }
+}
+
+#[async_trait]
+impl<E, T, C> AsyncQueryMeta<T, C> for E
+where
+    E: EndpointMeta + Sync,
+    E::Meta: 'static,
+    T: DeserializeOwned + 'static,
+    C: AsyncClient + Sync,
+{
+    async fn query_meta_async(
+        &self,
+        client: &C,
+    ) -> Result<Response<T, E::Meta>, ApiError<C::Error>> {
+        decode(query::send_async(self, client).await?, self.is_enveloped())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use http::StatusCode;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::api::{
+        game::PriceMeta,
+        test_client::{Get, TestClient},
+    };
+
+    impl EndpointMeta for Get {
+        type Meta = PriceMeta;
+    }
+
+    fn price_meta() -> PriceMeta {
+        PriceMeta {
+            region: Some("eu1".into()),
+            country: Some("DE".into()),
+            currency: Some("EUR".into()),
+        }
+    }
+
+    fn client() -> TestClient {
+        TestClient::new()
+            .respond(
+                "meta/",
+                r#"{
+                    "data": [1, 2],
+                    ".meta": { "region": "eu1", "country": "DE", "currency": "EUR" }
+                }"#,
+            )
+            .respond("no_meta/", r#"{ "data": [1, 2] }"#)
+            .respond("bare/", "[1, 2]")
+            .respond_with(
+                "error/",
+                StatusCode::BAD_REQUEST,
+                r#"{ "error": "invalid_plain", "message": "Invalid plain" }"#,
+            )
+    }
+
+    #[test]
+    fn with_meta_reads_the_envelope() {
+        let rsp: Response<Vec<u32>, Value> = with_meta(Get::new("meta/")).query(&client()).unwrap();
+        assert_eq!(rsp.data, vec![1, 2]);
+        assert_eq!(
+            rsp.meta,
+            json!({ "region": "eu1", "country": "DE", "currency": "EUR" })
+        );
+    }
+
+    #[test]
+    fn with_meta_defaults_missing_meta() {
+        let rsp: Response<Vec<u32>, PriceMeta> =
+            with_meta(Get::new("no_meta/")).query(&client()).unwrap();
+        assert_eq!(rsp.data, vec![1, 2]);
+        assert_eq!(rsp.meta, PriceMeta::default());
+    }
+
+    #[test]
+    fn with_meta_of_unenveloped_endpoints() {
+        let rsp: Response<Vec<u32>, Value> = with_meta(Get::unenveloped("bare/"))
+            .query(&client())
+            .unwrap();
+        assert_eq!(rsp.data, vec![1, 2]);
+        assert_eq!(rsp.meta, Value::Null);
+    }
+
+    #[test]
+    fn with_meta_reports_errors() {
+        let err = with_meta(Get::new("error/"))
+            .query(&client())
+            .map(|_: Response<Value, Value>| ())
+            .unwrap_err();
+        assert!(matches!(err, ApiError::ItadApi { msg } if msg == "Invalid plain"));
+    }
+
+    #[test]
+    fn query_meta_is_typed() {
+        let rsp: Response<Vec<u32>, PriceMeta> = Get::new("meta/").query_meta(&client()).unwrap();
+        assert_eq!(rsp.meta, price_meta());
+
+        let rsp: Response<Vec<u32>, PriceMeta> =
+            Get::unenveloped("bare/").query_meta(&client()).unwrap();
+        assert_eq!(rsp.data, vec![1, 2]);
+        assert_eq!(rsp.meta, PriceMeta::default());
+    }
+
+    #[test]
+    fn query_meta_async() {
+        let client = client();
+        let rsp: Response<Vec<u32>, PriceMeta> =
+            block_on(Get::new("meta/").query_meta_async(&client)).unwrap();
+        assert_eq!(rsp.data, vec![1, 2]);
+        assert_eq!(rsp.meta, price_meta());
+
+        let rsp: Response<Vec<u32>, PriceMeta> =
+            block_on(with_meta(Get::unenveloped("bare/")).query_async(&client)).unwrap();
+        assert_eq!(rsp.meta, PriceMeta::default());
+    }
+}
@@ -1,6 +1,16 @@
 use async_trait::async_trait;
+use bytes::Bytes;
+use http::{header, request::Builder as RequestBuilder, Request, Response};
+use serde::de::DeserializeOwned;
+use url::Url;
 
-use super::{ApiError, AsyncClient, Client};
+use super::{
+    common::Root, endpoint::Endpoint, instrument::QueryObserver, ApiError, AsyncClient, Client,
+    RestClient,
+};
+use crate::auth::AuthError;
+
+const REDACTED_QUERY_PARAMS: &[&str] = &["key", "access_token"];
 
 pub(crate) fn url_to_http_uri(url: url::Url) -> http::Uri {
     url.as_str()
@@ -8,6 +18,138 @@ pub(crate) fn url_to_http_uri(url: url::Url) -> http::Uri {
         .expect("failed to parse url::Url as http::Uri")
 }
 
+/// Replace the values of credential query parameters with a placeholder.
+pub(crate) fn redact_url(url: &Url) -> Url {
+    let mut redacted = url.clone();
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        let mut query_params = redacted.query_pairs_mut();
+        query_params.clear();
+        for (key, value) in pairs {
+            if REDACTED_QUERY_PARAMS.contains(&key.as_str()) {
+                query_params.append_pair(&key, "REDACTED");
+            } else {
+                query_params.append_pair(&key, &value);
+            }
+        }
+    }
+    redacted
+}
+
+/// Build the full URL for an endpoint, including credentials.
+pub(crate) fn endpoint_url<E, C>(endpoint: &E, client: &C) -> Result<Url, ApiError<C::Error>>
+where
+    E: Endpoint + ?Sized,
+    C: RestClient + ?Sized,
+{
+    let mut url = client.rest_endpoint(&endpoint.endpoint())?;
+    endpoint.set_query_parameters(&mut url)?;
+    if endpoint.requires_api_key() {
+        client.append_api_key_query_param(&mut url.query_pairs_mut())?;
+    }
+
+    if endpoint.requires_oauth_token() {
+        if let Some(granted) = client.oauth_token_scopes() {
+            let missing = endpoint
+                .oauth_scopes()
+                .iter()
+                .find(|scope| !granted.contains(scope));
+            if let Some(scope) = missing {
+                return Err(AuthError::MissingScope(*scope).into());
+            }
+        }
+        if !endpoint.uses_bearer_token() {
+            client.append_oauth_token_query_param(&mut url.query_pairs_mut())?;
+        }
+    }
+    Ok(url)
+}
+
+pub(crate) fn build_request<E, C>(
+    endpoint: &E,
+    client: &C,
+    url: Url,
+) -> Result<(RequestBuilder, Vec<u8>), ApiError<C::Error>>
+where
+    E: Endpoint + ?Sized,
+    C: RestClient + ?Sized,
+{
+    let mut req = Request::builder()
+        .method(endpoint.method())
+        .uri(url_to_http_uri(url));
+    if endpoint.requires_oauth_token() && endpoint.uses_bearer_token() {
+        req = req.header(header::AUTHORIZATION, client.oauth_bearer_header()?);
+    }
+    if let Some((mime, data)) = endpoint.body()? {
+        let req = req.header(header::CONTENT_TYPE, mime);
+        Ok((req, data))
+    } else {
+        Ok((req, Vec::new()))
+    }
+}
+
+/// Parse the response body as JSON, or the ITAD error if the request failed.
+pub(crate) fn response_value<E>(rsp: Response<Bytes>) -> Result<serde_json::Value, ApiError<E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let status = rsp.status();
+    let value = serde_json::from_slice(rsp.body())?;
+    if !status.is_success() {
+        return Err(ApiError::from_itad_api(value));
+    }
+    Ok(value)
+}
+
+/// Decode the response data, unwrapping it from the `data` member of the
+/// envelope if `enveloped`, or the ITAD error if the request failed.
+pub(crate) fn decode_response<T, E>(rsp: Response<Bytes>, enveloped: bool) -> Result<T, ApiError<E>>
+where
+    T: DeserializeOwned,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let value = response_value(rsp)?;
+    if enveloped {
+        serde_json::from_value::<Root<T>>(value).map(|root| root.data)
+    } else {
+        serde_json::from_value(value)
+    }
+    .map_err(ApiError::data_type::<T>)
+}
+
+/// Send the request for an endpoint and return the response, whatever its
+/// status.
+pub(crate) fn send<E, C>(endpoint: &E, client: &C) -> Result<Response<Bytes>, ApiError<C::Error>>
+where
+    E: Endpoint + ?Sized,
+    C: Client + ?Sized,
+{
+    let url = endpoint_url(endpoint, client)?;
+    let observer = QueryObserver::new(endpoint, &url);
+    let (req, data) = build_request(endpoint, client, url)?;
+    let rsp = observer.in_scope(|| client.rest(req, data));
+    observer.finish(rsp.as_ref().ok());
+    rsp
+}
+
+/// Send the request for an endpoint asynchronously and return the response,
+/// whatever its status.
+pub(crate) async fn send_async<E, C>(
+    endpoint: &E,
+    client: &C,
+) -> Result<Response<Bytes>, ApiError<C::Error>>
+where
+    E: Endpoint + Sync + ?Sized,
+    C: AsyncClient + Sync + ?Sized,
+{
+    let url = endpoint_url(endpoint, client)?;
+    let observer = QueryObserver::new(endpoint, &url);
+    let (req, data) = build_request(endpoint, client, url)?;
+    let rsp = observer.instrument(client.rest_async(req, data)).await;
+    observer.finish(rsp.as_ref().ok());
+    rsp
+}
+
 /// Query made to a client.
 pub trait Query<T, C>
 where
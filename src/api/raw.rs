@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::Response;
+
+use super::{endpoint::Endpoint, query, ApiError, AsyncClient, AsyncQuery, Client, Query};
+
+/// A query modifier which returns the raw response.
+///
+/// The response is returned whatever its status, only transport errors are
+/// reported as errors.
+#[derive(Debug, Clone)]
+pub struct Raw<E> {
+    endpoint: E,
+}
+
+/// Return the raw response of an endpoint.
+pub fn raw<E>(endpoint: E) -> Raw<E> {
+    Raw { endpoint }
+}
+
+impl<E, C> Query<Response<Bytes>, C> for Raw<E>
+where
+    E: Endpoint,
+    C: Client,
+{
+    fn query(&self, client: &C) -> Result<Response<Bytes>, ApiError<C::Error>> {
+        query::send(&self.endpoint, client)
+    }
+}
+
+#[async_trait]
+impl<E, C> AsyncQuery<Response<Bytes>, C> for Raw<E>
+where
+    E: Endpoint + Sync,
+    C: AsyncClient + Sync,
+{
+    async fn query_async(&self, client: &C) -> Result<Response<Bytes>, ApiError<C::Error>> {
+        query::send_async(&self.endpoint, client).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use http::StatusCode;
+
+    use super::*;
+    use crate::api::test_client::{Get, TestClient};
+
+    #[test]
+    fn returns_any_status() {
+        let client = TestClient::new()
+            .respond("ok/", r#"{ "data": 1 }"#)
+            .respond_with("missing/", StatusCode::NOT_FOUND, "not json");
+
+        let rsp = raw(Get::new("ok/")).query(&client).unwrap();
+        assert_eq!(rsp.status(), StatusCode::OK);
+        assert_eq!(rsp.body(), r#"{ "data": 1 }"#);
+
+        let rsp = block_on(raw(Get::new("missing/")).query_async(&client)).unwrap();
+        assert_eq!(rsp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(rsp.body(), "not json");
+    }
+
+    #[test]
+    fn reports_client_errors() {
+        let err = raw(Get::new("unknown/"))
+            .query(&TestClient::new())
+            .unwrap_err();
+        assert!(matches!(err, ApiError::Client { .. }));
+    }
+}
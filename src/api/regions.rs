@@ -0,0 +1,366 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Deserialize;
+
+use super::{
+    web::Regions, ApiError, AsyncClient, AsyncQuery, Client, Country, IdError, Query, Region,
+};
+
+struct BundledRegion {
+    region: &'static str,
+    code: &'static str,
+    sign: &'static str,
+    delimiter: &'static str,
+    left: bool,
+    name: &'static str,
+    countries: &'static [&'static str],
+}
+
+// Snapshot of `v01/web/regions/`; regenerate with
+// `cargo run --example bundled_regions`.
+const BUNDLED: &[BundledRegion] = &[
+    BundledRegion {
+        region: "eu1",
+        code: "EUR",
+        sign: "€",
+        delimiter: ",",
+        left: false,
+        name: "Euro",
+        countries: &[
+            "AD", "AT", "BE", "CH", "DE", "DK", "FI", "FR", "IE", "LI", "LU", "MK", "NL", "SE",
+        ],
+    },
+    BundledRegion {
+        region: "eu2",
+        code: "EUR",
+        sign: "€",
+        delimiter: ",",
+        left: false,
+        name: "Euro",
+        countries: &[
+            "AL", "BA", "BG", "CY", "CZ", "EE", "ES", "GR", "HR", "HU", "IT", "LT", "LV", "MC",
+            "ME", "MT", "NO", "PL", "PT", "RO", "RS", "SI", "SK", "SM", "VA",
+        ],
+    },
+    BundledRegion {
+        region: "uk",
+        code: "GBP",
+        sign: "£",
+        delimiter: ".",
+        left: true,
+        name: "British Pound Sterling",
+        countries: &["GB"],
+    },
+    BundledRegion {
+        region: "us",
+        code: "USD",
+        sign: "$",
+        delimiter: ".",
+        left: true,
+        name: "US Dollar",
+        countries: &["US"],
+    },
+    BundledRegion {
+        region: "ca",
+        code: "CAD",
+        sign: "$",
+        delimiter: ".",
+        left: true,
+        name: "Canadian Dollar",
+        countries: &["CA"],
+    },
+    BundledRegion {
+        region: "br2",
+        code: "BRL",
+        sign: "R$",
+        delimiter: ",",
+        left: true,
+        name: "Brazilian Real",
+        countries: &["BR"],
+    },
+    BundledRegion {
+        region: "au2",
+        code: "AUD",
+        sign: "$",
+        delimiter: ".",
+        left: true,
+        name: "Australian Dollar",
+        countries: &["AU"],
+    },
+    BundledRegion {
+        region: "ru",
+        code: "RUB",
+        sign: "₽",
+        delimiter: ",",
+        left: false,
+        name: "Russian Ruble",
+        countries: &["RU"],
+    },
+    BundledRegion {
+        region: "tr",
+        code: "TRY",
+        sign: "₺",
+        delimiter: ",",
+        left: false,
+        name: "Turkish Lira",
+        countries: &["TR"],
+    },
+    BundledRegion {
+        region: "cn",
+        code: "CNY",
+        sign: "¥",
+        delimiter: ".",
+        left: true,
+        name: "Chinese Yuan",
+        countries: &["CN"],
+    },
+];
+
+/// Check `country` against the bundled catalog, if it knows `region`.
+pub(crate) fn check_bundled(region: &Region<'_>, country: &Country<'_>) -> Result<(), IdError> {
+    match BUNDLED
+        .iter()
+        .find(|bundled| bundled.region == region.as_str())
+    {
+        Some(bundled) if !bundled.countries.contains(&country.as_str()) => {
+            Err(IdError::CountryNotInRegion {
+                country: country.to_string(),
+                region: region.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The default region for a country, according to the bundled catalog.
+pub fn default_region(country: &Country<'_>) -> Option<Region<'static>> {
+    BUNDLED
+        .iter()
+        .find(|bundled| bundled.countries.contains(&country.as_str()))
+        .map(|bundled| Region::new(bundled.region).expect("bundled regions are valid"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Currency {
+    pub code: String,
+    pub sign: String,
+    #[serde(default)]
+    pub delimiter: Option<String>,
+    /// Whether the sign goes to the left of the amount
+    #[serde(default)]
+    pub left: bool,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RegionInfo {
+    pub countries: BTreeSet<Country<'static>>,
+    pub currency: Currency,
+}
+
+/// Regions with their countries and currencies, as returned by [`Regions`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct RegionCatalog {
+    regions: BTreeMap<Region<'static>, RegionInfo>,
+}
+
+impl Default for RegionCatalog {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+impl RegionCatalog {
+    /// The catalog bundled with this crate.
+    pub fn bundled() -> Self {
+        Self {
+            regions: BUNDLED
+                .iter()
+                .map(|bundled| {
+                    let info = RegionInfo {
+                        countries: bundled
+                            .countries
+                            .iter()
+                            .map(|country| {
+                                Country::new(*country).expect("bundled countries are valid")
+                            })
+                            .collect(),
+                        currency: Currency {
+                            code: bundled.code.into(),
+                            sign: bundled.sign.into(),
+                            delimiter: Some(bundled.delimiter.into()),
+                            left: bundled.left,
+                            name: Some(bundled.name.into()),
+                        },
+                    };
+                    let region = Region::new(bundled.region).expect("bundled regions are valid");
+                    (region, info)
+                })
+                .collect(),
+        }
+    }
+
+    /// Fetch the current catalog.
+    pub fn fetch<C>(client: &C) -> Result<Self, ApiError<C::Error>>
+    where
+        C: Client,
+    {
+        Regions::default().query(client)
+    }
+
+    /// Fetch the current catalog asynchronously.
+    pub async fn fetch_async<C>(client: &C) -> Result<Self, ApiError<C::Error>>
+    where
+        C: AsyncClient + Sync,
+    {
+        Regions::default().query_async(client).await
+    }
+
+    pub fn get(&self, region: &Region<'_>) -> Option<&RegionInfo> {
+        self.regions.get(region.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Region<'static>, &RegionInfo)> {
+        self.regions.iter()
+    }
+
+    /// The first region that contains `country`.
+    pub fn region_of(&self, country: &Country<'_>) -> Option<&Region<'static>> {
+        self.regions
+            .iter()
+            .find(|(_, info)| info.countries.contains(country.as_str()))
+            .map(|(region, _)| region)
+    }
+
+    /// The currency used in `country`.
+    pub fn currency_of(&self, country: &Country<'_>) -> Option<&Currency> {
+        self.region_of(country)
+            .and_then(|region| self.regions.get(region))
+            .map(|info| &info.currency)
+    }
+
+    /// Check that `country` belongs to `region`, which must be in the
+    /// catalog.
+    pub fn check(&self, region: &Region<'_>, country: &Country<'_>) -> Result<(), IdError> {
+        match self.get(region) {
+            Some(info) if info.countries.contains(country.as_str()) => Ok(()),
+            Some(_) => Err(IdError::CountryNotInRegion {
+                country: country.to_string(),
+                region: region.to_string(),
+            }),
+            None => Err(IdError::Invalid {
+                kind: "region",
+                value: region.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::api::test_client::TestClient;
+
+    fn region(id: &str) -> Region<'_> {
+        Region::new(id).unwrap()
+    }
+
+    fn country(id: &str) -> Country<'_> {
+        Country::new(id).unwrap()
+    }
+
+    const PAYLOAD: &str = r#"{ "data": {
+        "eu1": {
+            "countries": ["DE", "FR"],
+            "currency": { "code": "EUR", "sign": "€", "delimiter": ",", "left": false, "name": "Euro" }
+        },
+        "us": {
+            "countries": ["US"],
+            "currency": { "code": "USD", "sign": "$" }
+        }
+    } }"#;
+
+    #[test]
+    fn check_bundled_pairs() {
+        assert_eq!(check_bundled(&region("eu1"), &country("de")), Ok(()));
+        assert_eq!(
+            check_bundled(&region("us"), &country("GB")),
+            Err(IdError::CountryNotInRegion {
+                country: "GB".into(),
+                region: "us".into(),
+            })
+        );
+        // Regions missing from the bundled catalog are left to the API.
+        assert_eq!(check_bundled(&region("newregion"), &country("GB")), Ok(()));
+    }
+
+    #[test]
+    fn default_region_of_bundled_countries() {
+        assert_eq!(default_region(&country("DE")), Some(region("eu1")));
+        assert_eq!(default_region(&country("pl")), Some(region("eu2")));
+        assert_eq!(default_region(&country("GB")), Some(region("uk")));
+        assert_eq!(default_region(&country("ZZ")), None);
+    }
+
+    #[test]
+    fn bundled_catalog() {
+        let catalog = RegionCatalog::default();
+        assert_eq!(catalog.iter().count(), BUNDLED.len());
+        assert_eq!(catalog.region_of(&country("US")), Some(&region("us")));
+        assert_eq!(catalog.region_of(&country("ZZ")), None);
+
+        let currency = catalog.currency_of(&country("BR")).unwrap();
+        assert_eq!(currency.code, "BRL");
+        assert_eq!(currency.sign, "R$");
+        assert!(currency.left);
+        assert_eq!(catalog.currency_of(&country("ZZ")), None);
+    }
+
+    #[test]
+    fn check_against_catalog() {
+        let catalog = RegionCatalog::bundled();
+        assert_eq!(catalog.check(&region("uk"), &country("GB")), Ok(()));
+        assert_eq!(
+            catalog.check(&region("uk"), &country("US")),
+            Err(IdError::CountryNotInRegion {
+                country: "US".into(),
+                region: "uk".into(),
+            })
+        );
+        assert_eq!(
+            catalog.check(&region("newregion"), &country("US")),
+            Err(IdError::Invalid {
+                kind: "region",
+                value: "newregion".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn deserializes_regions_payload() {
+        let client = TestClient::new().respond("v01/web/regions/", PAYLOAD);
+        let catalog = RegionCatalog::fetch(&client).unwrap();
+        assert_eq!(catalog.iter().count(), 2);
+
+        let eu1 = catalog.get(&region("eu1")).unwrap();
+        assert!(eu1.countries.contains("FR"));
+        assert_eq!(eu1.currency.delimiter.as_deref(), Some(","));
+        assert_eq!(eu1.currency.name.as_deref(), Some("Euro"));
+
+        let usd = catalog.currency_of(&country("US")).unwrap();
+        assert_eq!(usd.code, "USD");
+        assert_eq!(usd.delimiter, None);
+        assert!(!usd.left);
+        assert_eq!(usd.name, None);
+
+        assert_eq!(catalog.region_of(&country("GB")), None);
+        assert_eq!(
+            block_on(RegionCatalog::fetch_async(&client)).unwrap(),
+            catalog
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt::Write;
+
+use http::{header, HeaderMap, HeaderValue, Method, Request};
+use url::Url;
+
+use super::{endpoint::Endpoint, query, ApiError, RestClient};
+
+/// An endpoint rendered into the request a client would send for it.
+#[derive(Debug, Clone)]
+pub struct RenderedRequest {
+    method: Method,
+    url: Url,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+/// Render an endpoint into the request which `Query::query` would send,
+/// without sending it.
+pub fn render<E, C>(endpoint: &E, client: &C) -> Result<RenderedRequest, ApiError<C::Error>>
+where
+    E: Endpoint + ?Sized,
+    C: RestClient + ?Sized,
+{
+    let url = query::endpoint_url(endpoint, client)?;
+    let (req, body) = query::build_request(endpoint, client, url.clone())?;
+    Ok(RenderedRequest {
+        method: endpoint.method(),
+        url,
+        headers: req.headers_ref().cloned().unwrap_or_default(),
+        body,
+    })
+}
+
+impl RenderedRequest {
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Replace the API key, OAuth token and any `Authorization` header with a
+    /// placeholder.
+    pub fn redacted(&self) -> Self {
+        let mut headers = self.headers.clone();
+        if headers.contains_key(header::AUTHORIZATION) {
+            headers.insert(header::AUTHORIZATION, HeaderValue::from_static("REDACTED"));
+        }
+        Self {
+            method: self.method.clone(),
+            url: query::redact_url(&self.url),
+            headers,
+            body: self.body.clone(),
+        }
+    }
+
+    /// Format the request as a `curl` command line.
+    pub fn to_curl(&self) -> String {
+        let mut out = format!("curl -X {} {}", self.method, shell_quote(self.url.as_str()));
+        for (key, val) in &self.headers {
+            let val = String::from_utf8_lossy(val.as_bytes());
+            let _ = write!(out, " -H {}", shell_quote(&format!("{}: {}", key, val)));
+        }
+        if !self.body.is_empty() {
+            let _ = write!(
+                out,
+                " --data-binary {}",
+                shell_quote(&String::from_utf8_lossy(&self.body))
+            );
+        }
+        out
+    }
+
+    pub fn into_http_request(self) -> Result<Request<Vec<u8>>, http::Error> {
+        let mut req = Request::builder()
+            .method(self.method)
+            .uri(query::url_to_http_uri(self.url));
+        if let Some(headers) = req.headers_mut() {
+            *headers = self.headers;
+        }
+        req.body(self.body)
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+    use crate::api::{
+        error::BodyError,
+        test_client::{TestClient, API_KEY, OAUTH_TOKEN},
+    };
+
+    /// An endpoint with a query, a body and both credentials.
+    struct AddNote {
+        bearer: bool,
+    }
+
+    impl Endpoint for AddNote {
+        fn method(&self) -> Method {
+            Method::PUT
+        }
+
+        fn endpoint(&self) -> Cow<'static, str> {
+            "notes/".into()
+        }
+
+        fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+            Ok("title=Assassin's+Creed".into())
+        }
+
+        fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+            Ok(Some((
+                "application/json",
+                br#"{"note":"don't buy"}"#.to_vec(),
+            )))
+        }
+
+        fn requires_api_key(&self) -> bool {
+            true
+        }
+
+        fn requires_oauth_token(&self) -> bool {
+            true
+        }
+
+        fn uses_bearer_token(&self) -> bool {
+            self.bearer
+        }
+    }
+
+    #[test]
+    fn renders_the_request() {
+        let rendered = render(&AddNote { bearer: true }, &TestClient::new()).unwrap();
+        assert_eq!(rendered.method(), Method::PUT);
+        assert_eq!(
+            rendered.url().as_str(),
+            format!(
+                "https://api.test/notes/?title=Assassin%27s+Creed&key={}",
+                API_KEY
+            )
+        );
+        assert_eq!(
+            rendered.headers()[header::AUTHORIZATION],
+            format!("Bearer {}", OAUTH_TOKEN)
+        );
+        assert_eq!(rendered.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(rendered.body(), br#"{"note":"don't buy"}"#);
+    }
+
+    #[test]
+    fn redacts_credentials() {
+        let client = TestClient::new();
+        let rendered = render(&AddNote { bearer: true }, &client).unwrap();
+        let redacted = rendered.redacted();
+        assert_eq!(redacted.url().query_pairs().nth(1).unwrap().1, "REDACTED");
+        assert_eq!(redacted.headers()[header::AUTHORIZATION], "REDACTED");
+        assert_eq!(redacted.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(redacted.body(), rendered.body());
+        assert_eq!(rendered.url().query_pairs().nth(1).unwrap().1, API_KEY);
+
+        let rendered = render(&AddNote { bearer: false }, &client).unwrap();
+        assert!(!rendered.headers().contains_key(header::AUTHORIZATION));
+        let redacted = rendered.redacted();
+        assert!(!redacted.headers().contains_key(header::AUTHORIZATION));
+        assert_eq!(
+            redacted.url().query(),
+            Some("title=Assassin%27s+Creed&key=REDACTED&access_token=REDACTED")
+        );
+    }
+
+    #[test]
+    fn quotes_curl_arguments() {
+        let rendered = render(&AddNote { bearer: true }, &TestClient::new()).unwrap();
+        assert_eq!(
+            rendered.redacted().to_curl(),
+            "curl -X PUT 'https://api.test/notes/?title=Assassin%27s+Creed&key=REDACTED' \
+             -H 'authorization: REDACTED' -H 'content-type: application/json' \
+             --data-binary '{\"note\":\"don'\\''t buy\"}'"
+        );
+    }
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("''"), r"''\'''\'''");
+    }
+
+    #[test]
+    fn converts_into_http_request() {
+        let rendered = render(&AddNote { bearer: true }, &TestClient::new()).unwrap();
+        let request = rendered.clone().into_http_request().unwrap();
+        assert_eq!(request.method(), Method::PUT);
+        assert_eq!(request.uri().to_string(), rendered.url().as_str());
+        assert_eq!(request.headers(), rendered.headers());
+        assert_eq!(request.body(), rendered.body());
+    }
+}
@@ -2,16 +2,28 @@ use std::borrow::Cow;
 
 use derive_builder::Builder;
 use http::Method;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use super::endpoint::Endpoint;
+use super::{
+    endpoint::Endpoint,
+    v2::{search::SearchGames, GameSummary, GameType},
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+mod fuzzy;
+
+pub use fuzzy::{best_match, normalize_title, similarity, Match};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct FindGames<'a> {
     q: Cow<'a, str>,
+    #[builder(default)]
+    offset: Option<usize>,
+    #[builder(default)]
     limit: Option<usize>,
+    #[builder(default)]
     #[serde(serialize_with = "super::utils::serialize_option_bool_as_int")]
     strict: Option<bool>,
 }
@@ -39,3 +51,138 @@ impl Endpoint for FindGames<'_> {
         true
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SearchResult {
+    pub plain: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SearchResults {
+    #[serde(default)]
+    pub results: Vec<SearchResult>,
+}
+
+/// Filters for current API search results. The search endpoint only takes a
+/// title and a result count, so they are applied to the results it returns.
+#[derive(Debug, Clone, PartialEq, Builder)]
+pub struct GameFilter {
+    /// Only keep games of these types. Games of any type are kept if empty.
+    #[builder(setter(name = "_types"), private, default)]
+    types: Vec<GameType>,
+    /// Keep games marked as mature. Defaults to true.
+    #[builder(default = "true")]
+    mature: bool,
+}
+
+impl Default for GameFilter {
+    fn default() -> Self {
+        Self {
+            types: Vec::new(),
+            mature: true,
+        }
+    }
+}
+
+impl GameFilter {
+    pub fn builder() -> GameFilterBuilder {
+        GameFilterBuilder::default()
+    }
+
+    pub fn matches(&self, game: &GameSummary) -> bool {
+        let type_matches = self.types.is_empty()
+            || game
+                .type_field
+                .as_ref()
+                .is_some_and(|type_field| self.types.contains(type_field));
+        type_matches && (self.mature || !game.mature)
+    }
+}
+
+impl GameFilterBuilder {
+    pub fn game_type(&mut self, type_field: GameType) -> &mut Self {
+        self.types.get_or_insert_with(Vec::new).push(type_field);
+        self
+    }
+
+    pub fn game_types<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = GameType>,
+    {
+        self.types.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+}
+
+/// Search legacy plains for `title` and pick the closest match scoring at
+/// least `min_score`.
+pub fn best_plain<C>(
+    client: &C,
+    title: &str,
+    min_score: f64,
+) -> Result<Option<Match<SearchResult>>, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let endpoint = FindGames::builder().q(title).build().expect("q is set");
+    let found: SearchResults = endpoint.query(client)?;
+    Ok(best_match(title, found.results, min_score, |result| {
+        &result.title
+    }))
+}
+
+/// Search legacy plains for `title` asynchronously.
+pub async fn best_plain_async<C>(
+    client: &C,
+    title: &str,
+    min_score: f64,
+) -> Result<Option<Match<SearchResult>>, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let endpoint = FindGames::builder().q(title).build().expect("q is set");
+    let found: SearchResults = endpoint.query_async(client).await?;
+    Ok(best_match(title, found.results, min_score, |result| {
+        &result.title
+    }))
+}
+
+/// Search current API games for `title` and pick the closest match passing
+/// `filter` and scoring at least `min_score`.
+pub fn best_game<C>(
+    client: &C,
+    title: &str,
+    filter: &GameFilter,
+    min_score: f64,
+) -> Result<Option<Match<GameSummary>>, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let endpoint = SearchGames::builder()
+        .title(title)
+        .build()
+        .expect("title is set");
+    let found: Vec<GameSummary> = endpoint.query(client)?;
+    let found = found.into_iter().filter(|game| filter.matches(game));
+    Ok(best_match(title, found, min_score, |game| &game.title))
+}
+
+/// Search current API games for `title` asynchronously.
+pub async fn best_game_async<C>(
+    client: &C,
+    title: &str,
+    filter: &GameFilter,
+    min_score: f64,
+) -> Result<Option<Match<GameSummary>>, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let endpoint = SearchGames::builder()
+        .title(title)
+        .build()
+        .expect("title is set");
+    let found: Vec<GameSummary> = endpoint.query_async(client).await?;
+    let found = found.into_iter().filter(|game| filter.matches(game));
+    Ok(best_match(title, found, min_score, |game| &game.title))
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+/// Words which only describe an edition when they come before "edition".
+const EDITION_QUALIFIERS: &[&str] = &[
+    "anniversary",
+    "collectors",
+    "complete",
+    "definitive",
+    "deluxe",
+    "digital",
+    "enhanced",
+    "gold",
+    "legendary",
+    "premium",
+    "special",
+    "standard",
+    "ultimate",
+];
+
+/// Phrases which describe an edition wherever they appear.
+const EDITION_PHRASES: &[&[&str]] = &[
+    &["game", "of", "the", "year"],
+    &["goty"],
+    &["directors", "cut"],
+    &["remastered"],
+];
+
+/// Numerals to write as digits. "v" and "x" are left out: on their own they
+/// are as often a letter, as in "Mega Man X" or "V Rising".
+const ROMAN_NUMERALS: &[(&str, &str)] = &[
+    ("ii", "2"),
+    ("iii", "3"),
+    ("iv", "4"),
+    ("vi", "6"),
+    ("vii", "7"),
+    ("viii", "8"),
+    ("ix", "9"),
+    ("xi", "11"),
+    ("xii", "12"),
+    ("xiii", "13"),
+    ("xiv", "14"),
+    ("xv", "15"),
+    ("xvi", "16"),
+];
+
+/// Normalize a title for comparison: lowercase, without trademark symbols,
+/// punctuation or edition names, and with roman numerals as digits.
+pub fn normalize_title(title: &str) -> String {
+    let mut cleaned = String::with_capacity(title.len());
+    for c in title.chars() {
+        match c {
+            '™' | '®' | '©' | '\'' | '’' => {}
+            '&' => cleaned.push_str(" and "),
+            c if c.is_alphanumeric() => cleaned.extend(c.to_lowercase()),
+            _ => cleaned.push(' '),
+        }
+    }
+
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    let mut out: Vec<&str> = Vec::with_capacity(words.len());
+    let mut i = 0;
+    'words: while i < words.len() {
+        for phrase in EDITION_PHRASES {
+            if words[i..].starts_with(phrase) {
+                i += phrase.len();
+                continue 'words;
+            }
+        }
+        if words[i] == "edition" {
+            while out
+                .last()
+                .is_some_and(|word| EDITION_QUALIFIERS.contains(word))
+            {
+                out.pop();
+            }
+            i += 1;
+            continue;
+        }
+        let word = ROMAN_NUMERALS
+            .iter()
+            .find(|(roman, _)| *roman == words[i])
+            .map_or(words[i], |(_, digits)| *digits);
+        out.push(word);
+        i += 1;
+    }
+    out.join(" ")
+}
+
+fn bigrams(s: &str) -> HashMap<(char, char), usize> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut out = HashMap::new();
+    for pair in chars.windows(2) {
+        *out.entry((pair[0], pair[1])).or_insert(0) += 1;
+    }
+    out
+}
+
+/// Similarity of two titles after normalization, from 0.0 to 1.0.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize_title(a), normalize_title(b));
+    if a == b {
+        return 1.0;
+    }
+    let (a, b) = (bigrams(&a), bigrams(&b));
+    let total: usize = a.values().sum::<usize>() + b.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let shared: usize = a
+        .iter()
+        .map(|(pair, count)| (*count).min(b.get(pair).copied().unwrap_or(0)))
+        .sum();
+    2.0 * shared as f64 / total as f64
+}
+
+/// A candidate and its similarity to the title it was matched against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<T> {
+    pub item: T,
+    pub score: f64,
+}
+
+/// Pick the candidate whose title is most similar to `title`, if it scores at
+/// least `min_score`. Earlier candidates win ties.
+pub fn best_match<T, I, F>(title: &str, candidates: I, min_score: f64, key: F) -> Option<Match<T>>
+where
+    I: IntoIterator<Item = T>,
+    F: Fn(&T) -> &str,
+{
+    let mut best: Option<Match<T>> = None;
+    for item in candidates {
+        let score = similarity(title, key(&item));
+        if score >= min_score && best.as_ref().is_none_or(|best| score > best.score) {
+            best = Some(Match { item, score });
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_strips_symbols_and_punctuation() {
+        assert_eq!(
+            normalize_title("Tom Clancy’s Rainbow Six® Siege™"),
+            "tom clancys rainbow six siege"
+        );
+        assert_eq!(
+            normalize_title("Ori and the Will of the Wisps"),
+            normalize_title("Ori & the Will of the Wisps")
+        );
+        assert_eq!(normalize_title("  Half-Life:  Alyx "), "half life alyx");
+    }
+
+    #[test]
+    fn normalize_drops_editions() {
+        assert_eq!(
+            normalize_title("The Witcher 3: Wild Hunt - Game of the Year Edition"),
+            "the witcher 3 wild hunt"
+        );
+        assert_eq!(normalize_title("Fallout 4 GOTY"), "fallout 4");
+        assert_eq!(
+            normalize_title("Death Stranding Director's Cut"),
+            "death stranding"
+        );
+        assert_eq!(normalize_title("Dark Souls Remastered"), "dark souls");
+        assert_eq!(normalize_title("Skyrim Special Edition"), "skyrim");
+        assert_eq!(
+            normalize_title("Doom Eternal Deluxe Edition"),
+            "doom eternal"
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_qualifiers_outside_editions() {
+        assert_eq!(normalize_title("Gold Rush: The Game"), "gold rush the game");
+        assert_eq!(
+            normalize_title("Ultimate Chicken Horse"),
+            "ultimate chicken horse"
+        );
+    }
+
+    #[test]
+    fn normalize_converts_roman_numerals() {
+        assert_eq!(normalize_title("Final Fantasy VII"), "final fantasy 7");
+        assert_eq!(
+            normalize_title("Civilization VI"),
+            normalize_title("Civilization 6")
+        );
+        assert_eq!(normalize_title("Half-Life II"), "half life 2");
+    }
+
+    #[test]
+    fn normalize_keeps_single_letter_numerals() {
+        assert_eq!(normalize_title("Mega Man X"), "mega man x");
+        assert_ne!(
+            normalize_title("Mega Man X"),
+            normalize_title("Mega Man 10")
+        );
+        assert_eq!(normalize_title("V Rising"), "v rising");
+    }
+
+    #[test]
+    fn similarity_of_equal_titles() {
+        assert_eq!(similarity("Portal 2", "PORTAL 2™"), 1.0);
+        assert_eq!(similarity("Final Fantasy VII", "Final Fantasy 7"), 1.0);
+    }
+
+    #[test]
+    fn similarity_orders_closer_titles_higher() {
+        let close = similarity("The Witcher 3", "The Witcher 2");
+        let far = similarity("The Witcher 3", "Stardew Valley");
+        assert!(close > far);
+        assert!((0.0..1.0).contains(&close));
+        assert!(far < 0.3);
+    }
+
+    #[test]
+    fn similarity_is_symmetric() {
+        assert_eq!(
+            similarity("Hollow Knight", "Hollow Knight: Silksong"),
+            similarity("Hollow Knight: Silksong", "Hollow Knight")
+        );
+    }
+
+    #[test]
+    fn similarity_without_letters() {
+        assert_eq!(similarity("", "Portal"), 0.0);
+        assert_eq!(similarity("™", "Portal"), 0.0);
+    }
+
+    #[test]
+    fn best_match_picks_highest_score() {
+        let candidates = vec!["Portal", "Portal 2", "Portal Stories: Mel"];
+        let found = best_match("Portal 2™", candidates, 0.5, |title| title).unwrap();
+        assert_eq!(found.item, "Portal 2");
+        assert_eq!(found.score, 1.0);
+    }
+
+    #[test]
+    fn best_match_respects_min_score() {
+        let candidates = vec!["Stardew Valley", "Terraria"];
+        assert!(best_match("Portal 2", candidates, 0.5, |title| title).is_none());
+    }
+
+    #[test]
+    fn best_match_prefers_earlier_on_ties() {
+        let candidates = vec![("first", "Portal"), ("second", "Portal")];
+        let found = best_match("Portal", candidates, 0.0, |(_, title)| title).unwrap();
+        assert_eq!(found.item.0, "first");
+    }
+
+    #[test]
+    fn best_match_of_no_candidates() {
+        assert!(best_match("Portal", Vec::<&str>::new(), 0.0, |title| title).is_none());
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use serde::de::DeserializeOwned;
+use tower::Service;
+
+use super::{endpoint::Endpoint, ApiError, AsyncClient, AsyncQuery};
+
+/// A `tower` service which queries endpoints against a client and decodes the
+/// response as `T`.
+pub struct QueryService<C, T> {
+    client: C,
+    _data: PhantomData<fn() -> T>,
+}
+
+impl<C, T> QueryService<C, T> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            _data: PhantomData,
+        }
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+}
+
+impl<C, T> Clone for QueryService<C, T>
+where
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.client.clone())
+    }
+}
+
+impl<C, T> fmt::Debug for QueryService<C, T>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryService")
+            .field("client", &self.client)
+            .finish()
+    }
+}
+
+impl<C, T, E> Service<E> for QueryService<C, T>
+where
+    E: Endpoint + Send + Sync + 'static,
+    T: DeserializeOwned + Send + 'static,
+    C: AsyncClient + Clone + Send + Sync + 'static,
+{
+    type Response = T;
+    type Error = ApiError<C::Error>;
+    type Future = BoxFuture<'static, Result<T, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, endpoint: E) -> Self::Future {
+        let client = self.client.clone();
+        Box::pin(async move { endpoint.query_async(&client).await })
+    }
+}
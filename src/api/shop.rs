@@ -0,0 +1,322 @@
+use std::{collections::BTreeMap, convert::TryFrom, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    web::CoveredStores, ApiError, AsyncClient, AsyncQuery, Client, IdError, Query, ShopId,
+};
+
+macro_rules! shops {
+    ($($variant:ident => $id:literal, $name:literal, $homepage:literal;)*) => {
+        /// A shop covered by ITAD, by its legacy shop id.
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[non_exhaustive]
+        pub enum Shop {
+            $($variant,)*
+            /// A shop not in this catalog
+            Other(ShopId<'static>),
+        }
+
+        impl Shop {
+            /// Every shop in this catalog.
+            pub const KNOWN: &'static [Shop] = &[$(Shop::$variant,)*];
+
+            pub fn from_id(id: ShopId<'_>) -> Self {
+                match id.as_str() {
+                    $($id => Shop::$variant,)*
+                    _ => Shop::Other(id.into_owned()),
+                }
+            }
+
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Shop::$variant => $id,)*
+                    Shop::Other(id) => id.as_str(),
+                }
+            }
+
+            /// The name ITAD displays for the shop, if it is in this catalog.
+            pub fn name(&self) -> Option<&'static str> {
+                match self {
+                    $(Shop::$variant => Some($name),)*
+                    Shop::Other(_) => None,
+                }
+            }
+
+            /// The shop's homepage, if it is in this catalog.
+            pub fn homepage(&self) -> Option<&'static str> {
+                match self {
+                    $(Shop::$variant => Some($homepage),)*
+                    Shop::Other(_) => None,
+                }
+            }
+        }
+    };
+}
+
+shops! {
+    AllYouPlay => "allyouplay", "AllYouPlay", "https://www.allyouplay.com/";
+    Amazon => "amazonus", "Amazon", "https://www.amazon.com/";
+    BattleNet => "battlenet", "Battle.net", "https://shop.battle.net/";
+    Dlgamer => "dlgamer", "DLGamer", "https://www.dlgamer.com/";
+    Epic => "epic", "Epic Game Store", "https://store.epicgames.com/";
+    Fanatical => "bundlestars", "Fanatical", "https://www.fanatical.com/";
+    GameBillet => "gamebillet", "GameBillet", "https://www.gamebillet.com/";
+    GamersGate => "gamersgate", "GamersGate", "https://www.gamersgate.com/";
+    GamesPlanet => "gamesplanet", "GamesPlanet", "https://www.gamesplanet.com/";
+    Gog => "gog", "GOG", "https://www.gog.com/";
+    GreenManGaming => "greenmangaming", "GreenManGaming", "https://www.greenmangaming.com/";
+    HumbleStore => "humblestore", "Humble Store", "https://www.humblebundle.com/store";
+    IndieGala => "indiegalastore", "IndieGala Store", "https://www.indiegala.com/store";
+    ItchIo => "itchio", "itch.io", "https://itch.io/";
+    MacGameStore => "macgamestore", "MacGameStore", "https://www.macgamestore.com/";
+    Microsoft => "microsoft", "Microsoft Store", "https://www.microsoft.com/store/games";
+    Nuuvem => "nuuvem", "Nuuvem", "https://www.nuuvem.com/";
+    Origin => "origin", "Origin", "https://www.origin.com/";
+    Steam => "steam", "Steam", "https://store.steampowered.com/";
+    TwoGame => "2game", "2Game", "https://2game.com/";
+    Uplay => "uplay", "Ubisoft Store", "https://store.ubi.com/";
+    Voidu => "voidu", "Voidu", "https://www.voidu.com/";
+    WinGameStore => "wingamestore", "WinGameStore", "https://www.wingamestore.com/";
+}
+
+impl Display for Shop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Shop {
+    type Err = IdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Shop::from_id(ShopId::new(s)?))
+    }
+}
+
+impl TryFrom<&str> for Shop {
+    type Error = IdError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl<'a> From<ShopId<'a>> for Shop {
+    fn from(id: ShopId<'a>) -> Self {
+        Shop::from_id(id)
+    }
+}
+
+impl<'a> From<Shop> for ShopId<'a> {
+    fn from(shop: Shop) -> Self {
+        match shop {
+            Shop::Other(id) => id,
+            shop => ShopId::new(shop.as_str().to_owned()).expect("shop ids are valid"),
+        }
+    }
+}
+
+impl<'a> From<&'a Shop> for ShopId<'a> {
+    fn from(shop: &'a Shop) -> Self {
+        ShopId::new(shop.as_str()).expect("shop ids are valid")
+    }
+}
+
+impl Serialize for Shop {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Shop {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ShopId::deserialize(deserializer).map(Shop::from_id)
+    }
+}
+
+/// A shop as listed by [`CoveredStores`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ShopInfo {
+    #[serde(rename = "id")]
+    pub shop: Shop,
+    #[serde(rename = "title")]
+    pub name: String,
+    pub color: Option<String>,
+}
+
+impl ShopInfo {
+    pub fn homepage(&self) -> Option<&'static str> {
+        self.shop.homepage()
+    }
+}
+
+/// The shops covered by ITAD, seeded from [`Shop::KNOWN`] and refreshable
+/// from [`CoveredStores`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShopCatalog {
+    shops: BTreeMap<Shop, ShopInfo>,
+}
+
+impl Default for ShopCatalog {
+    fn default() -> Self {
+        Self::known()
+    }
+}
+
+impl ShopCatalog {
+    /// The shops known at compile time.
+    pub fn known() -> Self {
+        Self {
+            shops: Shop::KNOWN
+                .iter()
+                .map(|shop| {
+                    let info = ShopInfo {
+                        shop: shop.clone(),
+                        name: shop.name().unwrap_or_default().to_owned(),
+                        color: None,
+                    };
+                    (shop.clone(), info)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, shop: &Shop) -> Option<&ShopInfo> {
+        self.shops.get(shop)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ShopInfo> {
+        self.shops.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.shops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shops.is_empty()
+    }
+
+    /// Replace the catalog with the shops ITAD currently covers.
+    pub fn refresh<C>(&mut self, client: &C) -> Result<(), ApiError<C::Error>>
+    where
+        C: Client,
+    {
+        let shops: Vec<ShopInfo> = CoveredStores::new().query(client)?;
+        self.replace(shops);
+        Ok(())
+    }
+
+    /// Replace the catalog with the shops ITAD currently covers,
+    /// asynchronously.
+    pub async fn refresh_async<C>(&mut self, client: &C) -> Result<(), ApiError<C::Error>>
+    where
+        C: AsyncClient + Sync,
+    {
+        let shops: Vec<ShopInfo> = CoveredStores::new().query_async(client).await?;
+        self.replace(shops);
+        Ok(())
+    }
+
+    fn replace(&mut self, shops: Vec<ShopInfo>) {
+        self.shops = shops
+            .into_iter()
+            .map(|info| (info.shop.clone(), info))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::api::test_client::TestClient;
+
+    #[test]
+    fn parses_known_shops() {
+        assert_eq!("steam".parse::<Shop>().unwrap(), Shop::Steam);
+        assert_eq!(Shop::try_from("bundlestars").unwrap(), Shop::Fanatical);
+        assert_eq!(Shop::from(ShopId::new("2game").unwrap()), Shop::TwoGame);
+        for shop in Shop::KNOWN {
+            assert_eq!(&shop.as_str().parse::<Shop>().unwrap(), shop);
+            assert!(shop.name().is_some());
+            assert!(shop.homepage().is_some());
+        }
+    }
+
+    #[test]
+    fn keeps_unknown_shops() {
+        let shop: Shop = "newshop".parse().unwrap();
+        assert_eq!(shop, Shop::Other(ShopId::new("newshop").unwrap()));
+        assert_eq!(shop.as_str(), "newshop");
+        assert_eq!(shop.name(), None);
+        assert_eq!(shop.homepage(), None);
+        assert_eq!(ShopId::from(shop).as_str(), "newshop");
+    }
+
+    #[test]
+    fn rejects_invalid_ids() {
+        assert!("".parse::<Shop>().is_err());
+        assert!(Shop::try_from("not a shop").is_err());
+    }
+
+    #[test]
+    fn display_and_serde_round_trip() {
+        for shop in Shop::KNOWN
+            .iter()
+            .cloned()
+            .chain(Some("newshop".parse().unwrap()))
+        {
+            let json = serde_json::to_string(&shop).unwrap();
+            assert_eq!(json, format!("\"{}\"", shop));
+            assert_eq!(serde_json::from_str::<Shop>(&json).unwrap(), shop);
+            assert_eq!(shop.to_string().parse::<Shop>().unwrap(), shop);
+            assert_eq!(ShopId::from(&shop).as_str(), shop.to_string());
+        }
+        assert!(serde_json::from_str::<Shop>("\"\"").is_err());
+    }
+
+    #[test]
+    fn catalog_starts_with_known_shops() {
+        let catalog = ShopCatalog::default();
+        assert_eq!(catalog.len(), Shop::KNOWN.len());
+        let gog = catalog.get(&Shop::Gog).unwrap();
+        assert_eq!(gog.name, "GOG");
+        assert_eq!(gog.homepage(), Some("https://www.gog.com/"));
+        assert!(catalog.get(&"newshop".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn catalog_refreshes_from_covered_stores() {
+        let client = TestClient::new().respond(
+            "v01/web/stores/all/",
+            r##"{ "data": [
+                { "id": "steam", "title": "Steam", "color": "#1b2838" },
+                { "id": "newshop", "title": "New Shop", "color": null }
+            ] }"##,
+        );
+        let mut catalog = ShopCatalog::known();
+        catalog.refresh(&client).unwrap();
+        assert_eq!(catalog.len(), 2);
+        assert_eq!(
+            catalog.get(&Shop::Steam).unwrap().color.as_deref(),
+            Some("#1b2838")
+        );
+        let other = catalog.get(&"newshop".parse().unwrap()).unwrap();
+        assert_eq!(other.name, "New Shop");
+        assert_eq!(other.homepage(), None);
+        assert!(catalog.get(&Shop::Gog).is_none());
+
+        let mut refreshed = ShopCatalog::known();
+        block_on(refreshed.refresh_async(&client)).unwrap();
+        assert_eq!(refreshed, catalog);
+    }
+}
@@ -2,20 +2,26 @@ use std::borrow::Cow;
 
 use derive_builder::Builder;
 use http::Method;
+#[cfg(feature = "private-endpoints")]
+use serde::Deserialize;
 use serde::Serialize;
 
 use super::endpoint::Endpoint;
+#[cfg(feature = "private-endpoints")]
+use super::{Country, Plain, Region, ShopId};
 
 /// This module contains two private endpoints: `Waitlist Price Limits` and
 /// `Waitlist Cut Limits`. As these endpoints are private, and require explicit
-/// permission, they are not implemented here.
+/// permission, they are only available with the `private-endpoints` feature.
 
 // TODO: Pagination
 #[derive(Debug, Clone, PartialEq, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct WaitlistChart {
+    #[builder(default)]
     offset: Option<usize>,
+    #[builder(default)]
     limit: Option<usize>,
 }
 
@@ -48,7 +54,9 @@ impl Endpoint for WaitlistChart {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct CollectionChart {
+    #[builder(default)]
     offset: Option<usize>,
+    #[builder(default)]
     limit: Option<usize>,
 }
 
@@ -81,7 +89,9 @@ impl Endpoint for CollectionChart {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct PopularityChart {
+    #[builder(default)]
     offset: Option<usize>,
+    #[builder(default)]
     limit: Option<usize>,
 }
 
@@ -108,3 +118,198 @@ impl Endpoint for PopularityChart {
         true
     }
 }
+
+/// How many users wait for a game to drop below each price. Private, the API
+/// key needs explicit permission.
+#[cfg(feature = "private-endpoints")]
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+#[serde(rename_all = "snake_case")]
+pub struct WaitlistPriceLimits<'a> {
+    plain: Plain<'a>,
+    #[builder(default)]
+    shop: Option<ShopId<'a>>,
+    #[builder(default)]
+    region: Option<Region<'a>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+}
+
+#[cfg(feature = "private-endpoints")]
+impl<'a> WaitlistPriceLimits<'a> {
+    pub fn builder() -> WaitlistPriceLimitsBuilder<'a> {
+        WaitlistPriceLimitsBuilder::default()
+    }
+}
+
+#[cfg(feature = "private-endpoints")]
+impl Endpoint for WaitlistPriceLimits<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "v01/stats/waitlist/price/".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, super::error::BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "private-endpoints")]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PriceLimit {
+    pub price: f64,
+    pub count: u64,
+}
+
+/// How many users wait for a game to reach each discount. Private, the API key
+/// needs explicit permission.
+#[cfg(feature = "private-endpoints")]
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+#[serde(rename_all = "snake_case")]
+pub struct WaitlistCutLimits<'a> {
+    plain: Plain<'a>,
+    #[builder(default)]
+    shop: Option<ShopId<'a>>,
+}
+
+#[cfg(feature = "private-endpoints")]
+impl<'a> WaitlistCutLimits<'a> {
+    pub fn builder() -> WaitlistCutLimitsBuilder<'a> {
+        WaitlistCutLimitsBuilder::default()
+    }
+}
+
+#[cfg(feature = "private-endpoints")]
+impl Endpoint for WaitlistCutLimits<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "v01/stats/waitlist/cut/".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, super::error::BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "private-endpoints")]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CutLimit {
+    pub cut: u8,
+    pub count: u64,
+}
+
+#[cfg(all(test, feature = "private-endpoints"))]
+mod tests {
+    use super::*;
+    use crate::api::{render, test_client::TestClient, Query};
+
+    fn query(endpoint: &impl Endpoint) -> String {
+        render(endpoint, &TestClient::new())
+            .unwrap()
+            .redacted()
+            .url()
+            .query()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn price_limits_parameters() {
+        let endpoint = WaitlistPriceLimits::builder()
+            .plain(Plain::new("portal").unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(query(&endpoint), "plain=portal&key=REDACTED");
+
+        let endpoint = WaitlistPriceLimits::builder()
+            .plain(Plain::new("portal").unwrap())
+            .shop(ShopId::new("steam").unwrap())
+            .region(Region::new("eu1").unwrap())
+            .country(Country::new("de").unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(
+            query(&endpoint),
+            "plain=portal&shop=steam&region=eu1&country=DE&key=REDACTED"
+        );
+    }
+
+    #[test]
+    fn price_limits_require_a_plain() {
+        assert!(WaitlistPriceLimits::builder().build().is_err());
+    }
+
+    #[test]
+    fn price_limits_response() {
+        let client = TestClient::new().respond(
+            "v01/stats/waitlist/price/",
+            r#"{ "data": [{ "price": 4.99, "count": 120 }, { "price": 9.99, "count": 35 }] }"#,
+        );
+        let endpoint = WaitlistPriceLimits::builder()
+            .plain(Plain::new("portal").unwrap())
+            .build()
+            .unwrap();
+        let limits: Vec<PriceLimit> = endpoint.query(&client).unwrap();
+        assert_eq!(
+            limits,
+            vec![
+                PriceLimit {
+                    price: 4.99,
+                    count: 120
+                },
+                PriceLimit {
+                    price: 9.99,
+                    count: 35
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn cut_limits_parameters() {
+        let endpoint = WaitlistCutLimits::builder()
+            .plain(Plain::new("portal").unwrap())
+            .shop(ShopId::new("gog").unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(query(&endpoint), "plain=portal&shop=gog&key=REDACTED");
+    }
+
+    #[test]
+    fn cut_limits_response() {
+        let client = TestClient::new().respond(
+            "v01/stats/waitlist/cut/",
+            r#"{ "data": [{ "cut": 50, "count": 80 }, { "cut": 75, "count": 210 }] }"#,
+        );
+        let endpoint = WaitlistCutLimits::builder()
+            .plain(Plain::new("portal").unwrap())
+            .build()
+            .unwrap();
+        let limits: Vec<CutLimit> = endpoint.query(&client).unwrap();
+        assert_eq!(
+            limits,
+            vec![
+                CutLimit { cut: 50, count: 80 },
+                CutLimit {
+                    cut: 75,
+                    count: 210
+                }
+            ]
+        );
+    }
+}
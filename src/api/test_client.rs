@@ -0,0 +1,205 @@
+//! A client and endpoint for tests. The client answers from canned responses
+//! and records the requests it was sent.
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_timer::Delay;
+use http::{request::Builder as RequestBuilder, HeaderValue, Method, Response, StatusCode};
+use thiserror::Error;
+use url::{form_urlencoded::Serializer, Url, UrlQuery};
+
+use super::{endpoint::Endpoint, ApiError, AsyncClient, Client, RestClient};
+
+pub(crate) const API_KEY: &str = "test-key";
+pub(crate) const OAUTH_TOKEN: &str = "test-token";
+
+#[derive(Debug, Error)]
+#[error("no response for {}", path)]
+pub(crate) struct TestError {
+    path: String,
+}
+
+/// A `GET` of `path`, with or without a `data` envelope.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Get {
+    pub(crate) path: &'static str,
+    pub(crate) enveloped: bool,
+}
+
+impl Get {
+    pub(crate) fn new(path: &'static str) -> Self {
+        Self {
+            path,
+            enveloped: true,
+        }
+    }
+
+    pub(crate) fn unenveloped(path: &'static str) -> Self {
+        Self {
+            path,
+            enveloped: false,
+        }
+    }
+}
+
+impl Endpoint for Get {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        self.path.into()
+    }
+
+    fn is_enveloped(&self) -> bool {
+        self.enveloped
+    }
+}
+
+/// A request the client was sent.
+#[derive(Debug, Clone)]
+pub(crate) struct Recorded {
+    pub(crate) at: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct Canned {
+    status: StatusCode,
+    body: Bytes,
+    delay: Option<Duration>,
+}
+
+/// Answers requests by their endpoint path, such as `v01/game/prices/`,
+/// whatever their query.
+#[derive(Debug, Default)]
+pub(crate) struct TestClient {
+    responses: HashMap<String, Canned>,
+    requests: Mutex<Vec<Recorded>>,
+}
+
+impl TestClient {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer requests for `path` with `body` and a 200 status.
+    pub(crate) fn respond<B>(self, path: &str, body: B) -> Self
+    where
+        B: Into<Bytes>,
+    {
+        self.respond_with(path, StatusCode::OK, body)
+    }
+
+    pub(crate) fn respond_with<B>(mut self, path: &str, status: StatusCode, body: B) -> Self
+    where
+        B: Into<Bytes>,
+    {
+        self.responses.insert(
+            path.into(),
+            Canned {
+                status,
+                body: body.into(),
+                delay: None,
+            },
+        );
+        self
+    }
+
+    /// Delay asynchronous answers for `path`, which must have a response.
+    pub(crate) fn delay(mut self, path: &str, delay: Duration) -> Self {
+        self.responses
+            .get_mut(path)
+            .expect("delayed paths have a response")
+            .delay = Some(delay);
+        self
+    }
+
+    /// The requests sent so far, in the order they were sent.
+    pub(crate) fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn answer(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<(Canned, Response<Bytes>), ApiError<TestError>> {
+        let request = request.body(body).expect("test requests are valid");
+        let url = Url::parse(&request.uri().to_string()).expect("requests have valid urls");
+        let path = url.path().trim_start_matches('/').to_owned();
+        self.requests
+            .lock()
+            .unwrap()
+            .push(Recorded { at: Instant::now() });
+
+        let canned = self
+            .responses
+            .get(&path)
+            .cloned()
+            .ok_or_else(|| ApiError::client(TestError { path }))?;
+        let rsp = Response::builder()
+            .status(canned.status)
+            .body(canned.body.clone())
+            .unwrap();
+        Ok((canned, rsp))
+    }
+}
+
+impl RestClient for TestClient {
+    type Error = TestError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        Ok(Url::parse("https://api.test/")?.join(endpoint)?)
+    }
+
+    fn append_api_key_query_param(
+        &self,
+        query_params: &mut Serializer<'_, UrlQuery<'_>>,
+    ) -> Result<(), ApiError<Self::Error>> {
+        query_params.append_pair("key", API_KEY);
+        Ok(())
+    }
+
+    fn append_oauth_token_query_param(
+        &self,
+        query_params: &mut Serializer<'_, UrlQuery<'_>>,
+    ) -> Result<(), ApiError<Self::Error>> {
+        query_params.append_pair("access_token", OAUTH_TOKEN);
+        Ok(())
+    }
+
+    fn oauth_bearer_header(&self) -> Result<HeaderValue, ApiError<Self::Error>> {
+        Ok(HeaderValue::from_str(&format!("Bearer {}", OAUTH_TOKEN)).unwrap())
+    }
+}
+
+impl Client for TestClient {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        self.answer(request, body).map(|(_, rsp)| rsp)
+    }
+}
+
+#[async_trait]
+impl AsyncClient for TestClient {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let (canned, rsp) = self.answer(request, body)?;
+        if let Some(delay) = canned.delay {
+            Delay::new(delay).await;
+        }
+        Ok(rsp)
+    }
+}
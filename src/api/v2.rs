@@ -0,0 +1,18 @@
+//! Endpoints for the current ITAD API.
+//!
+//! Games are identified by UUID rather than by plain, and shops by a numeric
+//! id rather than by name.
+
+pub mod collection;
+pub mod deals;
+pub mod game;
+pub mod history;
+pub mod lookup;
+pub mod notes;
+pub mod notifications;
+pub mod search;
+pub mod stats;
+mod types;
+pub mod waitlist;
+
+pub use types::{Deal, DealFlag, GameSummary, GameType, GameUrls, LowPrice, Named, Price, ShopRef};
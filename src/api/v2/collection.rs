@@ -0,0 +1,491 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use derive_builder::Builder;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use super::types::{GameSummary, GameType, Price, ShopRef};
+use crate::{
+    api::{endpoint::Endpoint, error::BodyError},
+    auth::Scope,
+};
+
+/// Games on the user's collection. Returns a list of `CollectionGame`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CollectionGames {}
+
+impl CollectionGames {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Endpoint for CollectionGames {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "collection/games/v1".into()
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::CollectionRead]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CollectionGame {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub type_field: Option<GameType>,
+    #[serde(default)]
+    pub mature: bool,
+    pub added: String,
+}
+
+/// Add games to the user's collection. The response has no body, query it with
+/// `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct AddToCollection<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> AddToCollection<'a> {
+    pub fn builder() -> AddToCollectionBuilder<'a> {
+        AddToCollectionBuilder::default()
+    }
+}
+
+impl<'a> AddToCollectionBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for AddToCollection<'_> {
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "collection/games/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::CollectionWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Remove games from the user's collection. The response has no body, query it
+/// with `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct RemoveFromCollection<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> RemoveFromCollection<'a> {
+    pub fn builder() -> RemoveFromCollectionBuilder<'a> {
+        RemoveFromCollectionBuilder::default()
+    }
+}
+
+impl<'a> RemoveFromCollectionBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for RemoveFromCollection<'_> {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "collection/games/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::CollectionWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Copies of games the user owns. Returns a list of `CollectionCopy`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Copies {}
+
+impl Copies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Endpoint for Copies {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "collection/copies/v1".into()
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::CollectionRead]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CollectionCopy {
+    pub id: u64,
+    pub game: GameSummary,
+    #[serde(default)]
+    pub redeemed: bool,
+    pub shop: Option<ShopRef>,
+    pub price: Option<Price>,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub added: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CopyPrice<'a> {
+    pub amount: f64,
+    pub currency: Cow<'a, str>,
+}
+
+/// A copy of a game to add to the user's collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+#[serde(rename_all = "camelCase")]
+pub struct NewCopy<'a> {
+    game_id: Cow<'a, str>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redeemed: Option<bool>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shop: Option<u32>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    price: Option<CopyPrice<'a>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<Cow<'a, str>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<Cow<'a, str>>,
+}
+
+impl<'a> NewCopy<'a> {
+    pub fn builder() -> NewCopyBuilder<'a> {
+        NewCopyBuilder::default()
+    }
+}
+
+/// Changes to a copy in the user's collection. Fields which are not set are
+/// left unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct CopyUpdate<'a> {
+    id: u64,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redeemed: Option<bool>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shop: Option<u32>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    price: Option<CopyPrice<'a>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<Cow<'a, str>>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<Cow<'a, str>>>,
+}
+
+impl<'a> CopyUpdate<'a> {
+    pub fn builder() -> CopyUpdateBuilder<'a> {
+        CopyUpdateBuilder::default()
+    }
+}
+
+/// Add copies of games to the user's collection. The response has no body,
+/// query it with `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct AddCopies<'a> {
+    #[builder(setter(name = "_copies"), private)]
+    copies: Vec<NewCopy<'a>>,
+}
+
+impl<'a> AddCopies<'a> {
+    pub fn builder() -> AddCopiesBuilder<'a> {
+        AddCopiesBuilder::default()
+    }
+}
+
+impl<'a> AddCopiesBuilder<'a> {
+    pub fn copy(&mut self, copy: NewCopy<'a>) -> &mut Self {
+        self.copies.get_or_insert_with(Vec::new).push(copy);
+        self
+    }
+
+    pub fn copies<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = NewCopy<'a>>,
+    {
+        self.copies.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for AddCopies<'_> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "collection/copies/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some((
+            "application/json",
+            serde_json::to_vec(&self.copies)?,
+        )))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::CollectionWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Update copies in the user's collection. The response has no body, query it
+/// with `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct UpdateCopies<'a> {
+    #[builder(setter(name = "_updates"), private)]
+    updates: Vec<CopyUpdate<'a>>,
+}
+
+impl<'a> UpdateCopies<'a> {
+    pub fn builder() -> UpdateCopiesBuilder<'a> {
+        UpdateCopiesBuilder::default()
+    }
+}
+
+impl<'a> UpdateCopiesBuilder<'a> {
+    pub fn update(&mut self, update: CopyUpdate<'a>) -> &mut Self {
+        self.updates.get_or_insert_with(Vec::new).push(update);
+        self
+    }
+
+    pub fn updates<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = CopyUpdate<'a>>,
+    {
+        self.updates.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for UpdateCopies<'_> {
+    fn method(&self) -> Method {
+        Method::PATCH
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "collection/copies/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some((
+            "application/json",
+            serde_json::to_vec(&self.updates)?,
+        )))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::CollectionWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Delete copies from the user's collection by copy id. The response has no
+/// body, query it with `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct DeleteCopies {
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<u64>,
+}
+
+impl DeleteCopies {
+    pub fn builder() -> DeleteCopiesBuilder {
+        DeleteCopiesBuilder::default()
+    }
+}
+
+impl DeleteCopiesBuilder {
+    pub fn id(&mut self, id: u64) -> &mut Self {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id);
+        self
+    }
+
+    pub fn ids<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = u64>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for DeleteCopies {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "collection/copies/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::CollectionWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
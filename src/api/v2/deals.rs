@@ -0,0 +1,128 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use derive_builder::Builder;
+use http::Method;
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::types::{Deal, GameType};
+use crate::api::{deals::Direction, endpoint::Endpoint, error::BodyError, Country};
+
+#[derive(Debug, Clone)]
+pub enum DealsSorting {
+    Trending(Direction),
+    Time(Direction),
+    Price(Direction),
+    Cut(Direction),
+    Expiry(Direction),
+    Rank(Direction),
+}
+
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct DealsList<'a> {
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(default)]
+    offset: Option<usize>,
+    #[builder(default)]
+    limit: Option<usize>,
+    #[builder(default)]
+    #[serde(serialize_with = "serialize_sorting")]
+    sort: Option<DealsSorting>,
+    /// Include games which are not on sale
+    #[builder(default)]
+    nondeals: Option<bool>,
+    #[builder(default)]
+    mature: Option<bool>,
+    #[builder(setter(name = "_shops"), private, default)]
+    #[serde(serialize_with = "crate::api::utils::serialize_as_csv")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    shops: BTreeSet<u32>,
+    /// Encoded filter, as produced by the ITAD website
+    #[builder(default)]
+    filter: Option<Cow<'a, str>>,
+}
+
+impl<'a> DealsList<'a> {
+    pub fn builder() -> DealsListBuilder<'a> {
+        DealsListBuilder::default()
+    }
+}
+
+impl DealsListBuilder<'_> {
+    pub fn shop(&mut self, shop: u32) -> &mut Self {
+        self.shops.get_or_insert_with(BTreeSet::new).insert(shop);
+        self
+    }
+
+    pub fn shops<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = u32>,
+    {
+        self.shops.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for DealsList<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "deals/v2".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+fn serialize_sorting<S>(value: &Option<DealsSorting>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if let Some(value) = value {
+        let (field, direction) = match value {
+            DealsSorting::Trending(direction) => ("trending", direction),
+            DealsSorting::Time(direction) => ("time", direction),
+            DealsSorting::Price(direction) => ("price", direction),
+            DealsSorting::Cut(direction) => ("cut", direction),
+            DealsSorting::Expiry(direction) => ("expiry", direction),
+            DealsSorting::Rank(direction) => ("rank", direction),
+        };
+        match direction {
+            Direction::Asc => serializer.serialize_str(field),
+            Direction::Desc => serializer.serialize_str(&format!("-{}", field)),
+        }
+    } else {
+        serializer.serialize_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DealsEntry {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub type_field: Option<GameType>,
+    pub deal: Deal,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DealsPage {
+    pub next_offset: usize,
+    pub has_more: bool,
+    #[serde(default)]
+    pub list: Vec<DealsEntry>,
+}
@@ -0,0 +1,539 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use derive_builder::Builder;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use super::types::{Deal, GameSummary, GameType, GameUrls, LowPrice, Named, Price};
+use crate::api::{endpoint::Endpoint, error::BodyError, Country};
+
+/// Look up a game by title or Steam appid.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), build_fn(validate = "Self::validate"))]
+pub struct Lookup<'a> {
+    #[builder(default)]
+    title: Option<Cow<'a, str>>,
+    #[builder(default)]
+    appid: Option<u32>,
+}
+
+impl<'a> Lookup<'a> {
+    pub fn builder() -> LookupBuilder<'a> {
+        LookupBuilder::default()
+    }
+}
+
+impl LookupBuilder<'_> {
+    fn validate(&self) -> Result<(), String> {
+        if matches!(self.title, None | Some(None)) && matches!(self.appid, None | Some(None)) {
+            return Err("Either title or appid is required".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Endpoint for Lookup<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/lookup/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LookupResult {
+    pub found: bool,
+    pub game: Option<GameSummary>,
+}
+
+/// Detailed information about a game.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Info<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> Info<'a> {
+    pub fn builder() -> InfoBuilder<'a> {
+        InfoBuilder::default()
+    }
+}
+
+impl Endpoint for Info<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/info/v2".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameInfo {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub type_field: Option<GameType>,
+    #[serde(default)]
+    pub mature: bool,
+    #[serde(default)]
+    pub early_access: bool,
+    #[serde(default)]
+    pub achievements: bool,
+    #[serde(default)]
+    pub trading_cards: bool,
+    pub appid: Option<u32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub release_date: Option<String>,
+    #[serde(default)]
+    pub developers: Vec<Named>,
+    #[serde(default)]
+    pub publishers: Vec<Named>,
+    pub urls: Option<GameUrls>,
+}
+
+/// Current prices of games. The game ids are sent as a JSON array in the body.
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Prices<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    #[serde(skip)]
+    ids: BTreeSet<Cow<'a, str>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    /// Only return prices which are deals
+    #[builder(default)]
+    deals: Option<bool>,
+    #[builder(default)]
+    vouchers: Option<bool>,
+    /// Maximum number of prices returned per game
+    #[builder(default)]
+    capacity: Option<usize>,
+    #[builder(setter(name = "_shops"), private, default)]
+    #[serde(serialize_with = "crate::api::utils::serialize_as_csv")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    shops: BTreeSet<u32>,
+}
+
+impl<'a> Prices<'a> {
+    pub fn builder() -> PricesBuilder<'a> {
+        PricesBuilder::default()
+    }
+}
+
+impl<'a> PricesBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+
+    pub fn shop(&mut self, shop: u32) -> &mut Self {
+        self.shops.get_or_insert_with(BTreeSet::new).insert(shop);
+        self
+    }
+
+    pub fn shops<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = u32>,
+    {
+        self.shops.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for Prices<'_> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/prices/v3".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct HistoryLows {
+    pub all: Option<Price>,
+    pub y1: Option<Price>,
+    pub m3: Option<Price>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamePrices {
+    pub id: String,
+    pub history_low: Option<HistoryLows>,
+    #[serde(default)]
+    pub deals: Vec<Deal>,
+}
+
+/// Overview of the current best price and lowest recorded price of games. The
+/// game ids are sent as a JSON array in the body.
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Overview<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    #[serde(skip)]
+    ids: BTreeSet<Cow<'a, str>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(default)]
+    vouchers: Option<bool>,
+    #[builder(setter(name = "_shops"), private, default)]
+    #[serde(serialize_with = "crate::api::utils::serialize_as_csv")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    shops: BTreeSet<u32>,
+}
+
+impl<'a> Overview<'a> {
+    pub fn builder() -> OverviewBuilder<'a> {
+        OverviewBuilder::default()
+    }
+
+    /// Split into queries of at most `size` games each, for use with
+    /// `api::batch`.
+    pub fn chunks(&self, size: usize) -> Vec<Self> {
+        chunk_ids(&self.ids, size)
+            .into_iter()
+            .map(|ids| Self {
+                ids,
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    /// The subscriptions query for the same games and country.
+    pub fn subscriptions(&self) -> Subscriptions<'a> {
+        Subscriptions {
+            ids: self.ids.clone(),
+            country: self.country.clone(),
+        }
+    }
+}
+
+impl<'a> OverviewBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+
+    pub fn shop(&mut self, shop: u32) -> &mut Self {
+        self.shops.get_or_insert_with(BTreeSet::new).insert(shop);
+        self
+    }
+
+    pub fn shops<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = u32>,
+    {
+        self.shops.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for Overview<'_> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/overview/v2".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OverviewPrice {
+    pub id: String,
+    pub current: Option<Deal>,
+    pub lowest: Option<LowPrice>,
+    #[serde(default)]
+    pub bundled: u32,
+    pub urls: Option<GameUrls>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OverviewResult {
+    #[serde(default)]
+    pub prices: Vec<OverviewPrice>,
+    #[serde(default)]
+    pub bundles: Vec<serde_json::Value>,
+}
+
+/// The lowest recorded price of games. The game ids are sent as a JSON array
+/// in the body.
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct HistoryLow<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    #[serde(skip)]
+    ids: BTreeSet<Cow<'a, str>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_shops"), private, default)]
+    #[serde(serialize_with = "crate::api::utils::serialize_as_csv")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    shops: BTreeSet<u32>,
+}
+
+impl<'a> HistoryLow<'a> {
+    pub fn builder() -> HistoryLowBuilder<'a> {
+        HistoryLowBuilder::default()
+    }
+}
+
+impl<'a> HistoryLowBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+
+    pub fn shop(&mut self, shop: u32) -> &mut Self {
+        self.shops.get_or_insert_with(BTreeSet::new).insert(shop);
+        self
+    }
+
+    pub fn shops<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = u32>,
+    {
+        self.shops.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for HistoryLow<'_> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/historylow/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct HistoryLowEntry {
+    pub id: String,
+    pub low: Option<LowPrice>,
+}
+
+/// Subscription catalogs, such as Game Pass, which include games. The game ids
+/// are sent as a JSON array in the body. Returns a list of
+/// `GameSubscriptions`.
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Subscriptions<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    #[serde(skip)]
+    ids: BTreeSet<Cow<'a, str>>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+}
+
+impl<'a> Subscriptions<'a> {
+    pub fn builder() -> SubscriptionsBuilder<'a> {
+        SubscriptionsBuilder::default()
+    }
+
+    /// Split into queries of at most `size` games each, for use with
+    /// `api::batch`.
+    pub fn chunks(&self, size: usize) -> Vec<Self> {
+        chunk_ids(&self.ids, size)
+            .into_iter()
+            .map(|ids| Self {
+                ids,
+                ..self.clone()
+            })
+            .collect()
+    }
+}
+
+impl<'a> SubscriptionsBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for Subscriptions<'_> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/subs/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Subscription {
+    pub id: u32,
+    pub name: String,
+    /// When the game leaves the catalog, if announced
+    pub leaving: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GameSubscriptions {
+    pub id: String,
+    #[serde(default)]
+    pub subs: Vec<Subscription>,
+}
+
+fn chunk_ids<'a>(ids: &BTreeSet<Cow<'a, str>>, size: usize) -> Vec<BTreeSet<Cow<'a, str>>> {
+    let ids: Vec<_> = ids.iter().cloned().collect();
+    ids.chunks(size.max(1))
+        .map(|chunk| chunk.iter().cloned().collect())
+        .collect()
+}
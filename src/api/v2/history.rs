@@ -0,0 +1,454 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use derive_builder::Builder;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use super::types::{Price, ShopRef};
+use crate::api::{endpoint::Endpoint, error::BodyError, Country};
+
+/// The log of price changes of a game. Returns a `PriceHistory`.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct History<'a> {
+    id: Cow<'a, str>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_shops"), private, default)]
+    #[serde(serialize_with = "crate::api::utils::serialize_as_csv")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    shops: Vec<u32>,
+    /// Only return changes after this time
+    #[builder(default)]
+    since: Option<DateTime<Utc>>,
+}
+
+impl<'a> History<'a> {
+    pub fn builder() -> HistoryBuilder<'a> {
+        HistoryBuilder::default()
+    }
+}
+
+impl HistoryBuilder<'_> {
+    pub fn shop(&mut self, shop: u32) -> &mut Self {
+        self.shops.get_or_insert_with(Vec::new).push(shop);
+        self
+    }
+
+    pub fn shops<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = u32>,
+    {
+        self.shops.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for History<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/history/v2".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct HistoryEntry {
+    timestamp: DateTime<FixedOffset>,
+    shop: ShopRef,
+    deal: Option<HistoryDeal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct HistoryDeal {
+    price: Price,
+    regular: Price,
+    cut: u8,
+}
+
+/// A price change at a shop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryPoint {
+    pub timestamp: DateTime<FixedOffset>,
+    pub shop: ShopRef,
+    pub price: Price,
+    pub regular: Price,
+    pub cut: u8,
+}
+
+/// The lowest price of a day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyLow {
+    /// The day, in UTC
+    pub date: NaiveDate,
+    /// The price change that set the low. It may have happened on an earlier
+    /// day if the price was still in effect.
+    pub point: HistoryPoint,
+}
+
+/// A shop no longer selling the game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEnd {
+    pub timestamp: DateTime<FixedOffset>,
+    pub shop: ShopRef,
+}
+
+/// Price changes of a game, oldest first.
+///
+/// Each point is the price at its shop from its timestamp until the next point
+/// or end for the same shop.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(from = "Vec<HistoryEntry>")]
+pub struct PriceHistory {
+    pub points: Vec<HistoryPoint>,
+    pub ends: Vec<HistoryEnd>,
+}
+
+impl From<Vec<HistoryEntry>> for PriceHistory {
+    fn from(entries: Vec<HistoryEntry>) -> Self {
+        let mut history = Self::default();
+        for entry in entries {
+            match entry.deal {
+                Some(deal) => history.points.push(HistoryPoint {
+                    timestamp: entry.timestamp,
+                    shop: entry.shop,
+                    price: deal.price,
+                    regular: deal.regular,
+                    cut: deal.cut,
+                }),
+                None => history.ends.push(HistoryEnd {
+                    timestamp: entry.timestamp,
+                    shop: entry.shop,
+                }),
+            }
+        }
+        history.points.sort_by_key(|point| point.timestamp);
+        history.ends.sort_by_key(|end| end.timestamp);
+        history
+    }
+}
+
+/// A price being set, or ended if there is no point, at a shop.
+struct Change<'a> {
+    timestamp: DateTime<FixedOffset>,
+    shop: u32,
+    point: Option<&'a HistoryPoint>,
+}
+
+impl Change<'_> {
+    fn date(&self) -> NaiveDate {
+        self.timestamp.with_timezone(&Utc).date_naive()
+    }
+}
+
+fn lowest<'a, I>(points: I) -> Option<&'a HistoryPoint>
+where
+    I: IntoIterator<Item = &'a HistoryPoint>,
+{
+    points
+        .into_iter()
+        .min_by_key(|point| point.price.amount_int)
+}
+
+impl PriceHistory {
+    /// Points and ends merged, oldest first. An end sorts after a point with
+    /// the same timestamp.
+    fn changes(&self) -> Vec<Change<'_>> {
+        let mut changes: Vec<_> = self
+            .points
+            .iter()
+            .map(|point| Change {
+                timestamp: point.timestamp,
+                shop: point.shop.id,
+                point: Some(point),
+            })
+            .chain(self.ends.iter().map(|end| Change {
+                timestamp: end.timestamp,
+                shop: end.shop.id,
+                point: None,
+            }))
+            .collect();
+        changes.sort_by_key(|change| (change.timestamp, change.point.is_none()));
+        changes
+    }
+
+    /// Resample into the lowest price across all shops for each day, from the
+    /// day of the first change to the day of the last change. Days on which
+    /// no shop sold the game are left out.
+    pub fn daily_lows(&self) -> Vec<DailyLow> {
+        let changes = self.changes();
+        let (first, last) = match (changes.first(), changes.last()) {
+            (Some(first), Some(last)) => (first.date(), last.date()),
+            _ => return Vec::new(),
+        };
+
+        let mut lows = Vec::new();
+        let mut current: BTreeMap<u32, &HistoryPoint> = BTreeMap::new();
+        let mut changes = changes.iter().peekable();
+        let mut day = first;
+        while day <= last {
+            // A price ending during the day was still in effect for part of
+            // it.
+            let mut low = lowest(current.values().copied());
+            while let Some(change) = changes.next_if(|change| change.date() == day) {
+                match change.point {
+                    Some(point) => {
+                        current.insert(change.shop, point);
+                        low = lowest(low.into_iter().chain(Some(point)));
+                    }
+                    None => {
+                        current.remove(&change.shop);
+                    }
+                }
+            }
+            if let Some(low) = low {
+                lows.push(DailyLow {
+                    date: day,
+                    point: low.clone(),
+                });
+            }
+            day = match day.succ_opt() {
+                Some(day) => day,
+                None => break,
+            };
+        }
+        lows
+    }
+
+    /// The lowest price in effect at any shop between `from` and `to`,
+    /// including prices set before `from` which were still in effect.
+    pub fn lowest_in_window(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Option<&HistoryPoint> {
+        let mut current: BTreeMap<u32, &HistoryPoint> = BTreeMap::new();
+        let mut low = None;
+        for change in self.changes() {
+            if change.timestamp > to {
+                break;
+            }
+            match change.point {
+                Some(point) if change.timestamp <= from => {
+                    current.insert(change.shop, point);
+                }
+                Some(point) => low = lowest(low.into_iter().chain(Some(point))),
+                None if change.timestamp <= from => {
+                    current.remove(&change.shop);
+                }
+                None => {}
+            }
+        }
+        lowest(low.into_iter().chain(current.values().copied()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn price(amount_int: i64) -> Value {
+        json!({
+            "amount": amount_int as f64 / 100.0,
+            "amountInt": amount_int,
+            "currency": "EUR",
+        })
+    }
+
+    fn set(timestamp: &str, shop: u32, amount_int: i64) -> Value {
+        json!({
+            "timestamp": timestamp,
+            "shop": { "id": shop, "name": format!("shop {}", shop) },
+            "deal": { "price": price(amount_int), "regular": price(2000), "cut": 0 },
+        })
+    }
+
+    fn end(timestamp: &str, shop: u32) -> Value {
+        json!({
+            "timestamp": timestamp,
+            "shop": { "id": shop, "name": format!("shop {}", shop) },
+            "deal": null,
+        })
+    }
+
+    fn history(entries: Vec<Value>) -> PriceHistory {
+        serde_json::from_value(Value::Array(entries)).unwrap()
+    }
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    fn utc(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().unwrap()
+    }
+
+    fn lows(history: &PriceHistory) -> Vec<(NaiveDate, u32, i64)> {
+        history
+            .daily_lows()
+            .into_iter()
+            .map(|low| (low.date, low.point.shop.id, low.point.price.amount_int))
+            .collect()
+    }
+
+    #[test]
+    fn deserialize_splits_points_and_ends() {
+        let history = history(vec![
+            end("2024-01-03T00:00:00+00:00", 1),
+            set("2024-01-01T00:00:00+00:00", 1, 1000),
+        ]);
+        assert_eq!(history.points.len(), 1);
+        assert_eq!(history.ends.len(), 1);
+        assert_eq!(history.ends[0].shop.id, 1);
+    }
+
+    #[test]
+    fn daily_lows_carry_prices_over_gaps() {
+        let history = history(vec![
+            set("2024-01-01T12:00:00+00:00", 1, 1000),
+            set("2024-01-04T12:00:00+00:00", 1, 500),
+        ]);
+        assert_eq!(
+            lows(&history),
+            vec![
+                (date("2024-01-01"), 1, 1000),
+                (date("2024-01-02"), 1, 1000),
+                (date("2024-01-03"), 1, 1000),
+                (date("2024-01-04"), 1, 500),
+            ]
+        );
+        assert_eq!(
+            history.daily_lows()[2].point.timestamp,
+            history.points[0].timestamp
+        );
+    }
+
+    #[test]
+    fn daily_lows_take_lowest_of_overlapping_shops() {
+        let history = history(vec![
+            set("2024-01-01T08:00:00+00:00", 1, 1000),
+            set("2024-01-02T08:00:00+00:00", 2, 800),
+            set("2024-01-03T08:00:00+00:00", 1, 600),
+            set("2024-01-04T08:00:00+00:00", 1, 1200),
+        ]);
+        assert_eq!(
+            lows(&history),
+            vec![
+                (date("2024-01-01"), 1, 1000),
+                (date("2024-01-02"), 2, 800),
+                (date("2024-01-03"), 1, 600),
+                // Shop 1 was still at 6.00 until the change.
+                (date("2024-01-04"), 1, 600),
+            ]
+        );
+    }
+
+    #[test]
+    fn daily_lows_use_utc_days() {
+        let history = history(vec![
+            set("2024-01-01T23:30:00-02:00", 1, 1000),
+            set("2024-01-02T12:00:00+00:00", 1, 900),
+        ]);
+        assert_eq!(lows(&history), vec![(date("2024-01-02"), 1, 900)]);
+    }
+
+    #[test]
+    fn ended_price_no_longer_counts() {
+        let history = history(vec![
+            set("2024-01-01T08:00:00+00:00", 1, 500),
+            set("2024-01-01T08:00:00+00:00", 2, 900),
+            end("2024-01-02T08:00:00+00:00", 1),
+            set("2024-01-05T08:00:00+00:00", 2, 1500),
+        ]);
+        assert_eq!(
+            lows(&history),
+            vec![
+                (date("2024-01-01"), 1, 500),
+                // Shop 1 sold at 5.00 until the end.
+                (date("2024-01-02"), 1, 500),
+                (date("2024-01-03"), 2, 900),
+                (date("2024-01-04"), 2, 900),
+                (date("2024-01-05"), 2, 900),
+            ]
+        );
+    }
+
+    #[test]
+    fn daily_lows_skip_days_without_prices() {
+        let history = history(vec![
+            set("2024-01-01T08:00:00+00:00", 1, 500),
+            end("2024-01-01T20:00:00+00:00", 1),
+            set("2024-01-03T08:00:00+00:00", 1, 700),
+        ]);
+        assert_eq!(
+            lows(&history),
+            vec![(date("2024-01-01"), 1, 500), (date("2024-01-03"), 1, 700)]
+        );
+    }
+
+    #[test]
+    fn daily_lows_of_empty_history() {
+        assert!(PriceHistory::default().daily_lows().is_empty());
+    }
+
+    #[test]
+    fn lowest_in_window_includes_prices_in_effect() {
+        let history = history(vec![
+            set("2024-01-01T00:00:00+00:00", 1, 700),
+            set("2024-01-05T00:00:00+00:00", 2, 900),
+            set("2024-01-20T00:00:00+00:00", 2, 100),
+        ]);
+        let low = history
+            .lowest_in_window(utc("2024-01-10T00:00:00Z"), utc("2024-01-15T00:00:00Z"))
+            .unwrap();
+        assert_eq!((low.shop.id, low.price.amount_int), (1, 700));
+    }
+
+    #[test]
+    fn lowest_in_window_ignores_ended_prices() {
+        let history = history(vec![
+            set("2024-01-01T00:00:00+00:00", 1, 300),
+            set("2024-01-01T00:00:00+00:00", 2, 900),
+            end("2024-01-08T00:00:00+00:00", 1),
+            set("2024-01-12T00:00:00+00:00", 2, 800),
+        ]);
+        let low = history
+            .lowest_in_window(utc("2024-01-10T00:00:00Z"), utc("2024-01-15T00:00:00Z"))
+            .unwrap();
+        assert_eq!((low.shop.id, low.price.amount_int), (2, 800));
+
+        // The price ending inside the window was in effect at its start.
+        let low = history
+            .lowest_in_window(utc("2024-01-05T00:00:00Z"), utc("2024-01-15T00:00:00Z"))
+            .unwrap();
+        assert_eq!((low.shop.id, low.price.amount_int), (1, 300));
+    }
+
+    #[test]
+    fn lowest_in_window_without_prices() {
+        let history = history(vec![
+            set("2024-01-01T00:00:00+00:00", 1, 300),
+            end("2024-01-02T00:00:00+00:00", 1),
+        ]);
+        assert!(history
+            .lowest_in_window(utc("2024-01-05T00:00:00Z"), utc("2024-01-06T00:00:00Z"))
+            .is_none());
+    }
+}
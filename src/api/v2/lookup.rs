@@ -0,0 +1,124 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use derive_builder::Builder;
+use http::Method;
+
+use crate::api::{endpoint::Endpoint, error::BodyError};
+
+/// Look up game ids by their ids at a shop, such as `app/220` on Steam.
+/// Returns a map of shop ids to game ids, `None` for unknown games.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct GameIdsByShopId<'a> {
+    shop: u32,
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> GameIdsByShopId<'a> {
+    pub fn builder() -> GameIdsByShopIdBuilder<'a> {
+        GameIdsByShopIdBuilder::default()
+    }
+}
+
+impl<'a> GameIdsByShopIdBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for GameIdsByShopId<'_> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("lookup/id/shop/{}/v1", self.shop).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Look up the ids a shop uses for games. Returns a map of game ids to the
+/// list of shop ids, `None` for games the shop does not sell.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct ShopIdsByGameId<'a> {
+    shop: u32,
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> ShopIdsByGameId<'a> {
+    pub fn builder() -> ShopIdsByGameIdBuilder<'a> {
+        ShopIdsByGameIdBuilder::default()
+    }
+}
+
+impl<'a> ShopIdsByGameIdBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for ShopIdsByGameId<'_> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("lookup/shop/{}/id/v1", self.shop).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
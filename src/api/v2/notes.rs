@@ -0,0 +1,186 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use derive_builder::Builder;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{endpoint::Endpoint, error::BodyError},
+    auth::Scope,
+};
+
+/// The user's notes on games. Returns a list of `Note`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Notes {}
+
+impl Notes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Endpoint for Notes {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "user/notes/v1".into()
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::NotesRead]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Note<'a> {
+    /// Game id
+    pub gid: Cow<'a, str>,
+    pub note: Cow<'a, str>,
+}
+
+/// Set the user's notes on games, replacing any existing note. The response
+/// has no body, query it with `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct SetNotes<'a> {
+    #[builder(setter(name = "_notes"), private)]
+    notes: Vec<Note<'a>>,
+}
+
+impl<'a> SetNotes<'a> {
+    pub fn builder() -> SetNotesBuilder<'a> {
+        SetNotesBuilder::default()
+    }
+}
+
+impl<'a> SetNotesBuilder<'a> {
+    pub fn note<G, N>(&mut self, gid: G, note: N) -> &mut Self
+    where
+        G: Into<Cow<'a, str>>,
+        N: Into<Cow<'a, str>>,
+    {
+        self.notes.get_or_insert_with(Vec::new).push(Note {
+            gid: gid.into(),
+            note: note.into(),
+        });
+        self
+    }
+
+    pub fn notes<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = Note<'a>>,
+    {
+        self.notes.get_or_insert_with(Vec::new).extend(iter);
+        self
+    }
+}
+
+impl Endpoint for SetNotes<'_> {
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "user/notes/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.notes)?)))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::NotesWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Delete the user's notes on games. The response has no body, query it with
+/// `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct DeleteNotes<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> DeleteNotes<'a> {
+    pub fn builder() -> DeleteNotesBuilder<'a> {
+        DeleteNotesBuilder::default()
+    }
+}
+
+impl<'a> DeleteNotesBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for DeleteNotes<'_> {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "user/notes/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::NotesWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+use derive_builder::Builder;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{endpoint::Endpoint, error::BodyError},
+    auth::Scope,
+};
+
+/// The user's notifications. Returns a list of `Notification`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Notifications {}
+
+impl Notifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Endpoint for Notifications {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "notifications/v1".into()
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::NotificationsRead]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub title: String,
+    pub timestamp: String,
+    /// When the notification was read, if it was
+    pub read: Option<String>,
+}
+
+/// The games and deals of a waitlist notification.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct WaitlistNotification<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> WaitlistNotification<'a> {
+    pub fn builder() -> WaitlistNotificationBuilder<'a> {
+        WaitlistNotificationBuilder::default()
+    }
+}
+
+impl Endpoint for WaitlistNotification<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "notifications/waitlist/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::NotificationsRead]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Mark a notification as read. The response has no body, query it with
+/// `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct ReadNotification<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> ReadNotification<'a> {
+    pub fn builder() -> ReadNotificationBuilder<'a> {
+        ReadNotificationBuilder::default()
+    }
+}
+
+impl Endpoint for ReadNotification<'_> {
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "notifications/read/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::NotificationsWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Mark all notifications as read. The response has no body, query it with
+/// `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ReadAllNotifications {}
+
+impl ReadAllNotifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Endpoint for ReadAllNotifications {
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "notifications/read/all/v1".into()
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::NotificationsWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,45 @@
+use std::borrow::Cow;
+
+use derive_builder::Builder;
+use http::Method;
+use serde::Serialize;
+
+use crate::api::{endpoint::Endpoint, error::BodyError};
+
+/// Search games by title. Returns a list of `GameSummary`.
+#[derive(Debug, Clone, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct SearchGames<'a> {
+    title: Cow<'a, str>,
+    /// Maximum number of results
+    #[builder(default)]
+    results: Option<usize>,
+}
+
+impl<'a> SearchGames<'a> {
+    pub fn builder() -> SearchGamesBuilder<'a> {
+        SearchGamesBuilder::default()
+    }
+}
+
+impl Endpoint for SearchGames<'_> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "games/search/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,404 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use derive_builder::Builder;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{
+    endpoint::Endpoint, error::BodyError, ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// Page size the API uses when no limit is given.
+const DEFAULT_LIMIT: usize = 20;
+
+/// The offset and limit of the page after the one at `offset`.
+fn next_page(offset: Option<usize>, limit: Option<usize>) -> (usize, usize) {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    (offset.unwrap_or(0) + limit, limit)
+}
+
+/// Games on the most waitlists. Returns a list of `RankedEntry`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct MostWaitlisted {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl MostWaitlisted {
+    pub fn builder() -> MostWaitlistedBuilder {
+        MostWaitlistedBuilder::default()
+    }
+
+    /// The page following this one.
+    pub fn next_page(&self) -> Self {
+        let (offset, limit) = next_page(self.offset, self.limit);
+        Self {
+            offset: Some(offset),
+            limit: Some(limit),
+        }
+    }
+}
+
+impl Endpoint for MostWaitlisted {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "stats/most-waitlisted/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Games in the most collections. Returns a list of `RankedEntry`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct MostCollected {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl MostCollected {
+    pub fn builder() -> MostCollectedBuilder {
+        MostCollectedBuilder::default()
+    }
+
+    /// The page following this one.
+    pub fn next_page(&self) -> Self {
+        let (offset, limit) = next_page(self.offset, self.limit);
+        Self {
+            offset: Some(offset),
+            limit: Some(limit),
+        }
+    }
+}
+
+impl Endpoint for MostCollected {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "stats/most-collected/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// The most popular games, by waitlists and collections combined. Returns a
+/// list of `RankedEntry`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct MostPopular {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl MostPopular {
+    pub fn builder() -> MostPopularBuilder {
+        MostPopularBuilder::default()
+    }
+
+    /// The page following this one.
+    pub fn next_page(&self) -> Self {
+        let (offset, limit) = next_page(self.offset, self.limit);
+        Self {
+            offset: Some(offset),
+            limit: Some(limit),
+        }
+    }
+}
+
+impl Endpoint for MostPopular {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "stats/most-popular/v1".into()
+    }
+
+    fn query_parameters(&self) -> Result<Cow<'static, str>, BodyError> {
+        Ok(serde_urlencoded::to_string(self)?.into())
+    }
+
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RankedEntry {
+    pub position: u32,
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub count: u64,
+}
+
+/// A position and count in one chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rank {
+    pub position: u32,
+    pub count: u64,
+}
+
+/// A game's ranks in each chart, `None` where it is not on the chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardRow {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub waitlisted: Option<Rank>,
+    pub collected: Option<Rank>,
+    pub popular: Option<Rank>,
+}
+
+fn join(
+    waitlisted: Vec<RankedEntry>,
+    collected: Vec<RankedEntry>,
+    popular: Vec<RankedEntry>,
+) -> Vec<LeaderboardRow> {
+    let mut rows: Vec<LeaderboardRow> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let charts = [waitlisted, collected, popular];
+    for (chart, entries) in charts.iter().enumerate() {
+        for entry in entries {
+            let i = *index.entry(entry.id.clone()).or_insert_with(|| {
+                rows.push(LeaderboardRow {
+                    id: entry.id.clone(),
+                    slug: entry.slug.clone(),
+                    title: entry.title.clone(),
+                    waitlisted: None,
+                    collected: None,
+                    popular: None,
+                });
+                rows.len() - 1
+            });
+            let rank = Some(Rank {
+                position: entry.position,
+                count: entry.count,
+            });
+            match chart {
+                0 => rows[i].waitlisted = rank,
+                1 => rows[i].collected = rank,
+                _ => rows[i].popular = rank,
+            }
+        }
+    }
+    rows.sort_by_key(|row| {
+        (
+            row.popular.map_or(u32::MAX, |rank| rank.position),
+            row.waitlisted.map_or(u32::MAX, |rank| rank.position),
+            row.collected.map_or(u32::MAX, |rank| rank.position),
+        )
+    });
+    rows
+}
+
+/// Fetch the top `limit` entries of all three charts and join them by game,
+/// ordered by popularity, then waitlist and collection position.
+pub fn leaderboard<C>(client: &C, limit: usize) -> Result<Vec<LeaderboardRow>, ApiError<C::Error>>
+where
+    C: Client,
+{
+    let waitlisted = MostWaitlisted {
+        offset: None,
+        limit: Some(limit),
+    };
+    let collected = MostCollected {
+        offset: None,
+        limit: Some(limit),
+    };
+    let popular = MostPopular {
+        offset: None,
+        limit: Some(limit),
+    };
+    Ok(join(
+        waitlisted.query(client)?,
+        collected.query(client)?,
+        popular.query(client)?,
+    ))
+}
+
+/// Fetch and join the three charts asynchronously, querying them concurrently.
+pub async fn leaderboard_async<C>(
+    client: &C,
+    limit: usize,
+) -> Result<Vec<LeaderboardRow>, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+{
+    let waitlisted = MostWaitlisted {
+        offset: None,
+        limit: Some(limit),
+    };
+    let collected = MostCollected {
+        offset: None,
+        limit: Some(limit),
+    };
+    let popular = MostPopular {
+        offset: None,
+        limit: Some(limit),
+    };
+    let (waitlisted, collected, popular) = futures::try_join!(
+        waitlisted.query_async(client),
+        collected.query_async(client),
+        popular.query_async(client),
+    )?;
+    Ok(join(waitlisted, collected, popular))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    use super::*;
+    use crate::api::{render, test_client::TestClient};
+
+    fn entry(position: u32, id: &str, count: u64) -> RankedEntry {
+        RankedEntry {
+            position,
+            id: id.into(),
+            slug: id.into(),
+            title: id.to_uppercase(),
+            count,
+        }
+    }
+
+    fn rank(position: u32, count: u64) -> Option<Rank> {
+        Some(Rank { position, count })
+    }
+
+    #[test]
+    fn next_page() {
+        let first = MostWaitlisted::default();
+        let second = first.next_page();
+        assert_eq!(second.offset, Some(20));
+        assert_eq!(second.limit, Some(20));
+        assert_eq!(second.next_page().offset, Some(40));
+
+        let page = MostPopular::builder()
+            .offset(5usize)
+            .limit(10usize)
+            .build()
+            .unwrap();
+        let next = page.next_page();
+        assert_eq!((next.offset, next.limit), (Some(15), Some(10)));
+        assert_eq!(MostCollected::default().next_page().offset, Some(20));
+    }
+
+    #[test]
+    fn paging_parameters() {
+        let page = MostCollected::builder().limit(50usize).build().unwrap();
+        let rendered = render(&page.next_page(), &TestClient::new()).unwrap();
+        assert_eq!(
+            rendered.redacted().url().as_str(),
+            "https://api.test/stats/most-collected/v1?offset=50&limit=50&key=REDACTED"
+        );
+    }
+
+    #[test]
+    fn join_merges_charts_by_game() {
+        let rows = join(
+            vec![entry(1, "a", 300), entry(2, "b", 200)],
+            vec![entry(1, "b", 90), entry(2, "c", 80)],
+            vec![entry(1, "b", 500), entry(2, "a", 400)],
+        );
+        let ids: Vec<&str> = rows.iter().map(|row| row.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a", "c"]);
+
+        assert_eq!(rows[0].title, "B");
+        assert_eq!(rows[0].waitlisted, rank(2, 200));
+        assert_eq!(rows[0].collected, rank(1, 90));
+        assert_eq!(rows[0].popular, rank(1, 500));
+
+        assert_eq!(rows[1].waitlisted, rank(1, 300));
+        assert_eq!(rows[1].collected, None);
+        assert_eq!(rows[1].popular, rank(2, 400));
+
+        assert_eq!(rows[2].waitlisted, None);
+        assert_eq!(rows[2].collected, rank(2, 80));
+        assert_eq!(rows[2].popular, None);
+    }
+
+    #[test]
+    fn join_orders_unpopular_games_by_waitlist_then_collection() {
+        let rows = join(
+            vec![entry(2, "a", 20), entry(1, "b", 30)],
+            vec![entry(1, "c", 10), entry(2, "d", 5)],
+            vec![],
+        );
+        let ids: Vec<&str> = rows.iter().map(|row| row.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a", "c", "d"]);
+    }
+
+    fn client() -> TestClient {
+        let chart = |entries: Vec<(u32, &str, u64)>| {
+            json!(entries
+                .into_iter()
+                .map(|(position, id, count)| json!({
+                    "position": position,
+                    "id": id,
+                    "slug": id,
+                    "title": id.to_uppercase(),
+                    "count": count,
+                }))
+                .collect::<Vec<_>>())
+            .to_string()
+        };
+        TestClient::new()
+            .respond("stats/most-waitlisted/v1", chart(vec![(1, "a", 300)]))
+            .respond("stats/most-collected/v1", chart(vec![(1, "b", 90)]))
+            .respond(
+                "stats/most-popular/v1",
+                chart(vec![(1, "b", 500), (2, "a", 400)]),
+            )
+    }
+
+    #[test]
+    fn leaderboard_joins_all_charts() {
+        let client = client();
+        let rows = leaderboard(&client, 2).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].id, "b");
+        assert_eq!(rows[0].collected, rank(1, 90));
+        assert_eq!(rows[1].id, "a");
+        assert_eq!(rows[1].waitlisted, rank(1, 300));
+        assert_eq!(block_on(leaderboard_async(&client, 2)).unwrap(), rows);
+    }
+
+    #[test]
+    fn leaderboard_fails_with_any_chart() {
+        let client = TestClient::new().respond("stats/most-waitlisted/v1", "[]");
+        assert!(leaderboard(&client, 2).is_err());
+        assert!(block_on(leaderboard_async(&client, 2)).is_err());
+    }
+}
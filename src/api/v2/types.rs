@@ -0,0 +1,89 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameType {
+    Game,
+    Dlc,
+    Package,
+    #[serde(other)]
+    Other,
+}
+
+/// The basic details of a game, shared by several endpoints.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GameSummary {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub type_field: Option<GameType>,
+    #[serde(default)]
+    pub mature: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Price {
+    pub amount: f64,
+    pub amount_int: i64,
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ShopRef {
+    pub id: u32,
+    pub name: String,
+}
+
+/// An id and name pair, used for DRMs and platforms.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Named {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum DealFlag {
+    /// Historical low
+    H,
+    /// New historical low
+    N,
+    /// Store low
+    S,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deal {
+    pub shop: ShopRef,
+    pub price: Price,
+    pub regular: Price,
+    pub cut: u8,
+    pub voucher: Option<String>,
+    pub store_low: Option<Price>,
+    pub history_low: Option<Price>,
+    pub flag: Option<DealFlag>,
+    #[serde(default)]
+    pub drm: Vec<Named>,
+    #[serde(default)]
+    pub platforms: Vec<Named>,
+    pub timestamp: String,
+    pub expiry: Option<String>,
+    pub url: String,
+}
+
+/// The lowest recorded price of a game.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LowPrice {
+    pub shop: ShopRef,
+    pub price: Price,
+    pub regular: Price,
+    pub cut: u8,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GameUrls {
+    pub game: String,
+}
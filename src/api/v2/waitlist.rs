@@ -0,0 +1,191 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use derive_builder::Builder;
+use http::Method;
+use serde::Deserialize;
+
+use super::types::GameType;
+use crate::{
+    api::{endpoint::Endpoint, error::BodyError},
+    auth::Scope,
+};
+
+/// Games on the user's waitlist. Returns a list of `WaitlistGame`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WaitlistGames {}
+
+impl WaitlistGames {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Endpoint for WaitlistGames {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "waitlist/games/v1".into()
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::WaitlistRead]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WaitlistGame {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub type_field: Option<GameType>,
+    #[serde(default)]
+    pub mature: bool,
+    pub added: String,
+}
+
+/// Add games to the user's waitlist. The response has no body, query it with
+/// `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct AddToWaitlist<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> AddToWaitlist<'a> {
+    pub fn builder() -> AddToWaitlistBuilder<'a> {
+        AddToWaitlistBuilder::default()
+    }
+}
+
+impl<'a> AddToWaitlistBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for AddToWaitlist<'_> {
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "waitlist/games/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::WaitlistWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
+
+/// Remove games from the user's waitlist. The response has no body, query it
+/// with `api::ignore`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into, strip_option))]
+pub struct RemoveFromWaitlist<'a> {
+    #[builder(setter(name = "_ids"), private)]
+    ids: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> RemoveFromWaitlist<'a> {
+    pub fn builder() -> RemoveFromWaitlistBuilder<'a> {
+        RemoveFromWaitlistBuilder::default()
+    }
+}
+
+impl<'a> RemoveFromWaitlistBuilder<'a> {
+    pub fn id<T>(&mut self, id: T) -> &mut Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids.get_or_insert_with(BTreeSet::new).insert(id.into());
+        self
+    }
+
+    pub fn ids<I, T>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = T>,
+        T: Into<Cow<'a, str>>,
+    {
+        self.ids
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.map(Into::into));
+        self
+    }
+}
+
+impl Endpoint for RemoveFromWaitlist<'_> {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "waitlist/games/v1".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(&self.ids)?)))
+    }
+
+    fn requires_oauth_token(&self) -> bool {
+        true
+    }
+
+    fn uses_bearer_token(&self) -> bool {
+        true
+    }
+
+    fn oauth_scopes(&self) -> &'static [Scope] {
+        &[Scope::WaitlistWrite]
+    }
+
+    fn is_enveloped(&self) -> bool {
+        false
+    }
+}
@@ -1,16 +1,16 @@
-use std::{borrow::Cow, collections::BTreeSet};
+use std::{borrow::Cow, collections::BTreeSet, fmt::Display};
 
 use derive_builder::Builder;
 use http::Method;
 use serde::Serialize;
 
-use super::endpoint::Endpoint;
+use super::{endpoint::Endpoint, import::WaitlistImport, Plain, ShopId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct WaitlistCheck<'a> {
-    plain: Cow<'a, str>,
+    plain: Plain<'a>,
 }
 
 impl<'a> WaitlistCheck<'a> {
@@ -38,19 +38,38 @@ impl Endpoint for WaitlistCheck<'_> {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WaitlistOptions {
     Title,
     Gameid,
 }
 
+impl WaitlistOptions {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WaitlistOptions::Title => "title",
+            WaitlistOptions::Gameid => "gameid",
+        }
+    }
+}
+
+impl Display for WaitlistOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct Waitlist<'a> {
-    shop: Option<Cow<'a, str>>,
-    optional: Option<WaitlistOptions>,
+    #[builder(default)]
+    shop: Option<ShopId<'a>>,
+    #[builder(setter(name = "_optional"), private, default)]
+    #[serde(serialize_with = "super::utils::serialize_as_csv")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    optional: BTreeSet<WaitlistOptions>,
 }
 
 impl<'a> Waitlist<'a> {
@@ -59,6 +78,32 @@ impl<'a> Waitlist<'a> {
     }
 }
 
+impl WaitlistBuilder<'_> {
+    /// Ask for `value` only, replacing any options set before.
+    pub fn optional<VALUE>(&mut self, value: VALUE) -> &mut Self
+    where
+        VALUE: Into<WaitlistOptions>,
+    {
+        self.optional = Some(std::iter::once(value.into()).collect());
+        self
+    }
+
+    pub fn option(&mut self, option: WaitlistOptions) -> &mut Self {
+        self.optional
+            .get_or_insert_with(BTreeSet::new)
+            .insert(option);
+        self
+    }
+
+    pub fn options<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = WaitlistOptions>,
+    {
+        self.optional.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+}
+
 impl Endpoint for Waitlist<'_> {
     fn method(&self) -> Method {
         Method::GET
@@ -78,12 +123,10 @@ impl Endpoint for Waitlist<'_> {
     }
 }
 
-// TODO: Make body generic for T: Serialize?
 #[derive(Debug, Clone, Builder)]
 #[builder(setter(into, strip_option))]
 pub struct ImportWaitlistViaForm<'a> {
-    #[doc = r"JSON encoded waitlist to import. See: https://itad.docs.apiary.io/#reference/waitlist/import-via-form/send-user-to-waitlist-import-form"]
-    body: Cow<'a, str>,
+    body: WaitlistImport<'a>,
 }
 
 impl<'a> ImportWaitlistViaForm<'a> {
@@ -105,13 +148,14 @@ impl Endpoint for ImportWaitlistViaForm<'_> {
         #[derive(Debug, Serialize)]
         struct Body<'a> {
             file: Cow<'a, str>,
+            // `serde_urlencoded` cannot encode `()`, so the flag needs a value.
             #[serde(rename = "upload")]
-            _upload: (),
+            _upload: &'static str,
         }
-        let file = base64::encode(self.body.as_ref());
+        let file = base64::encode(serde_json::to_vec(&self.body)?);
         let body = Body {
             file: file.into(),
-            _upload: (),
+            _upload: "x",
         };
         Ok(Some((
             "application/x-www-form-urlencoded",
@@ -120,12 +164,11 @@ impl Endpoint for ImportWaitlistViaForm<'_> {
     }
 }
 
-// TODO: Make file generic for T: Serialize?
 #[derive(Debug, Clone, Serialize, Builder)]
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct ImportWaitlist<'a> {
-    file: Cow<'a, str>,
+    file: WaitlistImport<'a>,
     #[builder(default, setter(skip))]
     #[serde(rename = "upload")]
     _upload: (),
@@ -147,7 +190,7 @@ impl Endpoint for ImportWaitlist<'_> {
     }
 
     fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, super::error::BodyError> {
-        Ok(Some(("application/json", self.file.as_bytes().to_owned())))
+        Ok(Some(("application/json", serde_json::to_vec(&self.file)?)))
     }
 
     fn requires_oauth_token(&self) -> bool {
@@ -160,12 +203,13 @@ impl Endpoint for ImportWaitlist<'_> {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct WaitlistRemove<'a> {
-    #[builder(setter(name = "_plains"), private)]
+    #[builder(setter(name = "_plains"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
-    plains: BTreeSet<Cow<'a, str>>,
-    shop: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_ids"), private)]
+    plains: BTreeSet<Plain<'a>>,
+    #[builder(default)]
+    shop: Option<ShopId<'a>>,
+    #[builder(setter(name = "_ids"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     ids: BTreeSet<Cow<'a, str>>,
@@ -180,7 +224,7 @@ impl<'a> WaitlistRemove<'a> {
 impl<'a> WaitlistRemoveBuilder<'a> {
     pub fn plain<T>(&mut self, plain: T) -> &mut Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -191,7 +235,7 @@ impl<'a> WaitlistRemoveBuilder<'a> {
     pub fn plains<I, T>(&mut self, iter: I) -> &mut Self
     where
         I: Iterator<Item = T>,
-        T: Into<Cow<'a, str>>,
+        T: Into<Plain<'a>>,
     {
         self.plains
             .get_or_insert_with(BTreeSet::new)
@@ -237,3 +281,66 @@ impl Endpoint for WaitlistRemove<'_> {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{render, test_client::TestClient};
+
+    fn query(endpoint: &Waitlist<'_>) -> String {
+        render(endpoint, &TestClient::new())
+            .unwrap()
+            .redacted()
+            .url()
+            .query()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn optional_sets_a_single_option() {
+        let endpoint = Waitlist::builder()
+            .shop(ShopId::new("steam").unwrap())
+            .optional(WaitlistOptions::Gameid)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query(&endpoint),
+            "shop=steam&optional=gameid&access_token=REDACTED"
+        );
+
+        let endpoint = Waitlist::builder()
+            .option(WaitlistOptions::Gameid)
+            .optional(WaitlistOptions::Title)
+            .build()
+            .unwrap();
+        assert_eq!(query(&endpoint), "optional=title&access_token=REDACTED");
+    }
+
+    #[test]
+    fn options_combine() {
+        let endpoint = Waitlist::builder()
+            .optional(WaitlistOptions::Title)
+            .option(WaitlistOptions::Gameid)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query(&endpoint),
+            "optional=title%2Cgameid&access_token=REDACTED"
+        );
+        assert_eq!(
+            query(&Waitlist::builder().build().unwrap()),
+            "access_token=REDACTED"
+        );
+    }
+
+    #[test]
+    fn options_serialize_like_display() {
+        for option in [WaitlistOptions::Title, WaitlistOptions::Gameid] {
+            assert_eq!(
+                serde_json::to_string(&option).unwrap(),
+                format!("\"{}\"", option)
+            );
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::{borrow::Cow, collections::BTreeSet, fmt::Display};
 
 use http::Method;
 
-use super::endpoint::Endpoint;
+use super::{endpoint::Endpoint, Country, Region};
 use derive_builder::Builder;
 use serde::Serialize;
 
@@ -97,9 +97,10 @@ impl Display for StoresDisplayOptions {
 #[builder(setter(into, strip_option))]
 #[serde(rename_all = "snake_case")]
 pub struct StoresInRegion<'a> {
-    region: Cow<'a, str>,
-    country: Option<Cow<'a, str>>,
-    #[builder(setter(name = "_optional"), private)]
+    region: Region<'a>,
+    #[builder(default)]
+    country: Option<Country<'a>>,
+    #[builder(setter(name = "_optional"), private, default)]
     #[serde(serialize_with = "super::utils::serialize_as_csv")]
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     optional: BTreeSet<StoresDisplayOptions>,
@@ -1,7 +1,6 @@
-use std::fmt::Debug;
+use std::fmt::Display;
 
 use thiserror::Error;
-use url::{form_urlencoded::Serializer, UrlQuery};
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -10,45 +9,44 @@ pub enum AuthError {
     MissingApiKey,
     #[error("Missing Oauth token")]
     MissingOauthToken,
+    #[error("Invalid Oauth token")]
+    InvalidOauthToken,
+    #[error("Oauth token lacks the {} scope", .0)]
+    MissingScope(Scope),
 }
 
-#[derive(Clone)]
-pub(crate) struct Auth {
-    pub(crate) api_key: Option<String>,
-    pub(crate) oauth_token: Option<String>,
+/// OAuth scopes required by endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scope {
+    UserInfo,
+    WaitlistRead,
+    WaitlistWrite,
+    CollectionRead,
+    CollectionWrite,
+    NotesRead,
+    NotesWrite,
+    NotificationsRead,
+    NotificationsWrite,
 }
 
-impl Auth {
-    pub(crate) fn append_api_key_query_param(
-        &self,
-        query_params: &mut Serializer<'_, UrlQuery<'_>>,
-    ) -> Result<(), AuthError> {
-        self.api_key
-            .as_ref()
-            .map(|key| {
-                query_params.append_pair("key", key);
-            })
-            .ok_or(AuthError::MissingApiKey)
-    }
-
-    pub(crate) fn append_oauth_token_query_param(
-        &self,
-        query_params: &mut Serializer<'_, UrlQuery<'_>>,
-    ) -> Result<(), AuthError> {
-        self.oauth_token
-            .as_ref()
-            .map(|token| {
-                query_params.append_pair("access_token", token);
-            })
-            .ok_or(AuthError::MissingOauthToken)
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::UserInfo => "user_info",
+            Scope::WaitlistRead => "wait_read",
+            Scope::WaitlistWrite => "wait_write",
+            Scope::CollectionRead => "coll_read",
+            Scope::CollectionWrite => "coll_write",
+            Scope::NotesRead => "notes_read",
+            Scope::NotesWrite => "notes_write",
+            Scope::NotificationsRead => "notif_read",
+            Scope::NotificationsWrite => "notif_write",
+        }
     }
 }
 
-impl Debug for Auth {
+impl Display for Scope {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Auth")
-            .field("api_key", &self.api_key.is_some())
-            .field("oauth_token", &self.oauth_token.is_some())
-            .finish()
+        write!(f, "{}", self.as_str())
     }
 }
@@ -1,276 +1,226 @@
-use std::convert::TryInto;
-
-use async_trait::async_trait;
-use futures::TryFutureExt;
-use log::debug;
-use reqwest::{blocking::Client as HttpClient, Client as AsyncHttpClient};
-use url::Url;
-
-use crate::{
-    api,
-    auth::Auth,
-    error::{ItadApiResult, RestError},
-};
+use std::time::Duration;
+
+#[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+use reqwest::{Certificate, Proxy};
+
+use crate::auth::Scope;
+
+#[cfg(any(
+    feature = "reqwest-blocking",
+    feature = "reqwest-async",
+    feature = "ureq-client",
+    feature = "hyper-client",
+    feature = "tower"
+))]
+mod backend;
+#[cfg(feature = "reqwest-async")]
+mod coalesce;
+#[cfg(feature = "hyper-client")]
+mod hyper_client;
+#[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+mod reqwest_client;
+#[cfg(feature = "tower")]
+mod tower_client;
+#[cfg(feature = "ureq-client")]
+mod ureq_client;
+
+#[cfg(feature = "hyper-client")]
+pub use hyper_client::{HyperHttpsClient, ItadApiClientHyper};
+#[cfg(feature = "reqwest-blocking")]
+pub use reqwest_client::ItadApiClient;
+#[cfg(feature = "reqwest-async")]
+pub use reqwest_client::ItadApiClientAsync;
+#[cfg(feature = "tower")]
+pub use tower_client::ItadApiClientTower;
+#[cfg(feature = "ureq-client")]
+pub use ureq_client::ItadApiClientUreq;
+
+/// The state of a client's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests fail immediately with `RestError::CircuitOpen`.
+    Open,
+    /// The reset timeout has passed, the next request is let through as a
+    /// probe. A successful probe closes the circuit, a failure opens it again.
+    HalfOpen,
+}
 
-const DEFAULT_ITAD_API_HOST: &str = "api.isthereanydeal.com";
+/// Transport settings applied to the underlying HTTP client when the builder
+/// constructs one itself. Backends ignore the settings they do not support.
+#[derive(Debug, Default, Clone)]
+struct HttpOptions {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    proxies: Vec<Proxy>,
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    no_proxy: bool,
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    root_certificates: Vec<Certificate>,
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    tls_built_in_root_certs: Option<bool>,
+    #[cfg(feature = "gzip")]
+    gzip: Option<bool>,
+    #[cfg(feature = "brotli")]
+    brotli: Option<bool>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    user_agent: Option<String>,
+}
 
-#[derive(Clone, Debug)]
-pub struct ItadApiClient {
-    client: HttpClient,
-    rest_url: Url,
-    auth: Auth,
+#[derive(Debug, Default)]
+pub struct ItadApiBuilder {
+    host: Option<String>,
+    api_key: Option<String>,
+    oauth_token: Option<String>,
+    oauth_scopes: Option<Vec<Scope>>,
+    options: HttpOptions,
+    circuit_breaker: Option<(u32, Duration)>,
+    #[cfg(feature = "reqwest-blocking")]
+    http_client: Option<reqwest::blocking::Client>,
+    #[cfg(feature = "reqwest-async")]
+    async_http_client: Option<reqwest::Client>,
+    #[cfg(feature = "reqwest-async")]
+    coalesce_requests: bool,
+    #[cfg(feature = "ureq-client")]
+    ureq_agent: Option<ureq::Agent>,
+    #[cfg(feature = "hyper-client")]
+    hyper_client: Option<HyperHttpsClient>,
 }
 
-impl ItadApiClient {
-    pub fn new() -> ItadApiResult<Self> {
-        Self::new_impl::<&str, &str>(None, None, None)
+impl ItadApiBuilder {
+    pub fn new() -> Self {
+        ItadApiBuilder::default()
     }
 
-    pub fn with_api_key<S>(api_key: S) -> ItadApiResult<Self>
+    pub fn host<S>(&mut self, value: S) -> &mut Self
     where
         S: Into<String>,
     {
-        Self::new_impl(None, Some(api_key), None::<&str>)
+        self.host = Some(value.into());
+        self
     }
 
-    pub fn with_oauth_token<S>(oauth_token: S) -> ItadApiResult<Self>
+    pub fn api_key<S>(&mut self, value: S) -> &mut Self
     where
         S: Into<String>,
     {
-        Self::new_impl(None, None::<&str>, Some(oauth_token))
+        self.api_key = Some(value.into());
+        self
     }
 
-    fn new_impl<K, T>(
-        host: Option<&str>,
-        api_key: Option<K>,
-        oauth_token: Option<T>,
-    ) -> ItadApiResult<Self>
+    pub fn oauth_token<S>(&mut self, value: S) -> &mut Self
     where
-        K: Into<String>,
-        T: Into<String>,
+        S: Into<String>,
     {
-        let rest_url = Url::parse(&format!(
-            "https://{}/",
-            host.unwrap_or(DEFAULT_ITAD_API_HOST),
-        ))?;
-        let auth = Auth {
-            api_key: api_key.map(Into::into),
-            oauth_token: oauth_token.map(Into::into),
-        };
-
-        Ok(ItadApiClient {
-            client: HttpClient::new(),
-            rest_url,
-            auth,
-        })
-    }
-
-    pub fn builder() -> ItadApiBuilder {
-        ItadApiBuilder::new()
+        self.oauth_token = Some(value.into());
+        self
     }
-}
-
-impl api::RestClient for ItadApiClient {
-    type Error = RestError;
 
-    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
-        debug!("REST api call {}", endpoint);
-        self.rest_url
-            .join(endpoint.trim_start_matches('/'))
-            .map_err(From::from)
+    /// The scopes the OAuth token was granted. Queries of endpoints needing
+    /// other scopes then fail without being sent.
+    pub fn oauth_scopes<I>(&mut self, scopes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Scope>,
+    {
+        self.oauth_scopes = Some(scopes.into_iter().collect());
+        self
     }
 
-    fn append_api_key_query_param(
-        &self,
-        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
-    ) -> Result<(), api::ApiError<Self::Error>> {
-        Ok(self.auth.append_api_key_query_param(query_params)?)
+    /// Total timeout for a request, from connecting until the body has been
+    /// read.
+    ///
+    /// There is no separate read timeout: reqwest 0.11 only offers a total
+    /// and a connect timeout, so a slow body is bounded by this one.
+    pub fn timeout(&mut self, value: Duration) -> &mut Self {
+        self.options.timeout = Some(value);
+        self
     }
 
-    fn append_oauth_token_query_param(
-        &self,
-        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
-    ) -> Result<(), api::ApiError<Self::Error>> {
-        Ok(self.auth.append_oauth_token_query_param(query_params)?)
-    }
-}
-
-impl api::Client for ItadApiClient {
-    fn rest(
-        &self,
-        request: http::request::Builder,
-        body: Vec<u8>,
-    ) -> Result<http::Response<bytes::Bytes>, api::ApiError<Self::Error>> {
-        let call = || -> Result<_, RestError> {
-            let http_request = request.body(body)?;
-            let request = http_request.try_into()?;
-            let rsp = self.client.execute(request)?;
-
-            let mut http_rsp = http::Response::builder()
-                .status(rsp.status())
-                .version(rsp.version());
-            let headers = http_rsp.headers_mut().unwrap();
-            for (key, val) in rsp.headers() {
-                headers.insert(key, val.clone());
-            }
-            http_rsp.body(rsp.bytes()?).map_err(From::from)
-        };
-        call().map_err(api::ApiError::client)
+    pub fn connect_timeout(&mut self, value: Duration) -> &mut Self {
+        self.options.connect_timeout = Some(value);
+        self
     }
-}
-
-#[derive(Clone, Debug)]
-pub struct ItadApiClientAsync {
-    client: AsyncHttpClient,
-    rest_url: Url,
-    auth: Auth,
-}
 
-impl ItadApiClientAsync {
-    pub fn new() -> ItadApiResult<Self> {
-        Self::new_impl::<&str, &str>(None, None, None)
+    /// Add a proxy. May be called multiple times, proxies are tried in the
+    /// order they were added.
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    pub fn proxy(&mut self, proxy: Proxy) -> &mut Self {
+        self.options.proxies.push(proxy);
+        self
     }
 
-    pub fn with_api_key<S>(api_key: S) -> ItadApiResult<Self>
-    where
-        S: Into<String>,
-    {
-        Self::new_impl(None, Some(api_key), None::<&str>)
+    /// Disable all proxies, including those picked up from the environment.
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    pub fn no_proxy(&mut self) -> &mut Self {
+        self.options.no_proxy = true;
+        self
     }
 
-    pub fn with_oauth_token<S>(oauth_token: S) -> ItadApiResult<Self>
-    where
-        S: Into<String>,
-    {
-        Self::new_impl(None, None::<&str>, Some(oauth_token))
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    pub fn root_certificate(&mut self, cert: Certificate) -> &mut Self {
+        self.options.root_certificates.push(cert);
+        self
     }
 
-    fn new_impl<K, T>(
-        host: Option<&str>,
-        api_key: Option<K>,
-        oauth_token: Option<T>,
-    ) -> ItadApiResult<Self>
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    pub fn root_certificates<I>(&mut self, iter: I) -> &mut Self
     where
-        K: Into<String>,
-        T: Into<String>,
+        I: Iterator<Item = Certificate>,
     {
-        let rest_url = Url::parse(&format!(
-            "https://{}/",
-            host.unwrap_or(DEFAULT_ITAD_API_HOST)
-        ))?;
-        let client = AsyncHttpClient::new();
-        let auth = Auth {
-            api_key: api_key.map(Into::into),
-            oauth_token: oauth_token.map(Into::into),
-        };
-        let api = Self {
-            client,
-            rest_url,
-            auth,
-        };
-        Ok(api)
-    }
-}
-
-impl api::RestClient for ItadApiClientAsync {
-    type Error = RestError;
-
-    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
-        debug!("REST api call {}", endpoint);
-        self.rest_url
-            .join(endpoint.trim_start_matches('/'))
-            .map_err(From::from)
-    }
-
-    fn append_api_key_query_param(
-        &self,
-        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
-    ) -> Result<(), api::ApiError<Self::Error>> {
-        Ok(self.auth.append_api_key_query_param(query_params)?)
+        self.options.root_certificates.extend(iter);
+        self
     }
 
-    fn append_oauth_token_query_param(
-        &self,
-        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
-    ) -> Result<(), api::ApiError<Self::Error>> {
-        Ok(self.auth.append_oauth_token_query_param(query_params)?)
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    pub fn tls_built_in_root_certs(&mut self, enable: bool) -> &mut Self {
+        self.options.tls_built_in_root_certs = Some(enable);
+        self
     }
-}
 
-#[async_trait]
-impl api::AsyncClient for ItadApiClientAsync {
-    async fn rest_async(
-        &self,
-        request: http::request::Builder,
-        body: Vec<u8>,
-    ) -> Result<http::Response<bytes::Bytes>, api::ApiError<Self::Error>> {
-        let call = || async {
-            let http_request = request.body(body)?;
-            let request = http_request.try_into()?;
-            let rsp = self.client.execute(request).await?;
-
-            let mut http_rsp = http::Response::builder()
-                .status(rsp.status())
-                .version(rsp.version());
-            let headers = http_rsp.headers_mut().unwrap();
-            for (key, val) in rsp.headers() {
-                headers.insert(key, val.clone());
-            }
-            http_rsp.body(rsp.bytes().await?).map_err(From::from)
-        };
-        call().map_err(api::ApiError::client).await
+    #[cfg(feature = "gzip")]
+    pub fn gzip(&mut self, enable: bool) -> &mut Self {
+        self.options.gzip = Some(enable);
+        self
     }
-}
 
-#[derive(Debug, Default)]
-pub struct ItadApiBuilder {
-    host: Option<String>,
-    api_key: Option<String>,
-    oauth_token: Option<String>,
-}
-
-impl ItadApiBuilder {
-    pub fn new() -> Self {
-        ItadApiBuilder::default()
+    #[cfg(feature = "brotli")]
+    pub fn brotli(&mut self, enable: bool) -> &mut Self {
+        self.options.brotli = Some(enable);
+        self
     }
 
-    pub fn host<S>(&mut self, value: S) -> &mut Self
-    where
-        S: Into<String>,
-    {
-        self.host = Some(value.into());
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.options.pool_max_idle_per_host = Some(max);
         self
     }
 
-    pub fn api_key<S>(&mut self, value: S) -> &mut Self
-    where
-        S: Into<String>,
-    {
-        self.api_key = Some(value.into());
+    pub fn pool_idle_timeout(&mut self, value: Duration) -> &mut Self {
+        self.options.pool_idle_timeout = Some(value);
         self
     }
 
-    pub fn oauth_token<S>(&mut self, value: S) -> &mut Self
+    pub fn user_agent<S>(&mut self, value: S) -> &mut Self
     where
         S: Into<String>,
     {
-        self.oauth_token = Some(value.into());
+        self.options.user_agent = Some(value.into());
         self
     }
 
-    pub fn build(&self) -> ItadApiResult<ItadApiClient> {
-        ItadApiClient::new_impl(
-            self.host.as_deref(),
-            self.api_key.as_ref(),
-            self.oauth_token.as_ref(),
-        )
-    }
-
-    pub fn build_async(&self) -> ItadApiResult<ItadApiClientAsync> {
-        ItadApiClientAsync::new_impl(
-            self.host.as_deref(),
-            self.api_key.as_ref(),
-            self.oauth_token.as_ref(),
-        )
+    /// Fail fast with `RestError::CircuitOpen` after `failure_threshold`
+    /// consecutive failed requests. Once `reset_timeout` has passed a single
+    /// probe request is let through to check whether the service recovered.
+    ///
+    /// Transport errors and 5xx responses count as failures.
+    pub fn circuit_breaker(
+        &mut self,
+        failure_threshold: u32,
+        reset_timeout: Duration,
+    ) -> &mut Self {
+        self.circuit_breaker = Some((failure_threshold, reset_timeout));
+        self
     }
 }
@@ -0,0 +1,97 @@
+//! Pieces shared by all HTTP backends.
+use std::fmt::Debug;
+
+use http::HeaderValue;
+use url::{form_urlencoded::Serializer, Url, UrlQuery};
+
+use super::ItadApiBuilder;
+use crate::{
+    auth::{AuthError, Scope},
+    error::ItadApiResult,
+};
+
+pub(super) mod circuit;
+
+const DEFAULT_ITAD_API_HOST: &str = "api.isthereanydeal.com";
+
+pub(super) fn rest_url(host: Option<&str>) -> ItadApiResult<Url> {
+    Ok(Url::parse(&format!(
+        "https://{}/",
+        host.unwrap_or(DEFAULT_ITAD_API_HOST)
+    ))?)
+}
+
+pub(super) fn auth<K, T>(api_key: Option<K>, oauth_token: Option<T>) -> Auth
+where
+    K: Into<String>,
+    T: Into<String>,
+{
+    Auth {
+        api_key: api_key.map(Into::into),
+        oauth_token: oauth_token.map(Into::into),
+        oauth_scopes: None,
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Auth {
+    pub(crate) api_key: Option<String>,
+    pub(crate) oauth_token: Option<String>,
+    pub(crate) oauth_scopes: Option<Vec<Scope>>,
+}
+
+impl Auth {
+    pub(crate) fn append_api_key_query_param(
+        &self,
+        query_params: &mut Serializer<'_, UrlQuery<'_>>,
+    ) -> Result<(), AuthError> {
+        self.api_key
+            .as_ref()
+            .map(|key| {
+                query_params.append_pair("key", key);
+            })
+            .ok_or(AuthError::MissingApiKey)
+    }
+
+    pub(crate) fn append_oauth_token_query_param(
+        &self,
+        query_params: &mut Serializer<'_, UrlQuery<'_>>,
+    ) -> Result<(), AuthError> {
+        self.oauth_token
+            .as_ref()
+            .map(|token| {
+                query_params.append_pair("access_token", token);
+            })
+            .ok_or(AuthError::MissingOauthToken)
+    }
+
+    pub(crate) fn oauth_bearer_header(&self) -> Result<HeaderValue, AuthError> {
+        let token = self
+            .oauth_token
+            .as_ref()
+            .ok_or(AuthError::MissingOauthToken)?;
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| AuthError::InvalidOauthToken)?;
+        value.set_sensitive(true);
+        Ok(value)
+    }
+}
+
+impl Debug for Auth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Auth")
+            .field("api_key", &self.api_key.is_some())
+            .field("oauth_token", &self.oauth_token.is_some())
+            .field("oauth_scopes", &self.oauth_scopes)
+            .finish()
+    }
+}
+
+impl ItadApiBuilder {
+    pub(super) fn new_circuit_breaker(&self) -> Option<circuit::CircuitBreaker> {
+        self.circuit_breaker
+            .map(|(failure_threshold, reset_timeout)| {
+                circuit::CircuitBreaker::new(failure_threshold, reset_timeout)
+            })
+    }
+}
@@ -0,0 +1,258 @@
+#[cfg(any(feature = "reqwest-async", feature = "hyper-client", feature = "tower"))]
+use std::future::Future;
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http::Response;
+
+use crate::{client::CircuitState, error::RestError};
+
+#[derive(Debug)]
+struct Inner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// The id of the probe in flight and when it was let through.
+    probe: Option<(u64, Instant)>,
+    next_probe: u64,
+}
+
+/// Permission to send a request, handed back to `record` with its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Permit {
+    probe: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            reset_timeout,
+            inner: Arc::new(Mutex::new(Inner {
+                consecutive_failures: 0,
+                opened_at: None,
+                probe: None,
+                next_probe: 0,
+            })),
+        }
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        match inner.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.reset_timeout => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Check whether a request may be sent.
+    fn acquire(&self) -> Result<Permit, RestError> {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let opened_at = match inner.opened_at {
+            None => return Ok(Permit { probe: None }),
+            Some(opened_at) => opened_at,
+        };
+        if opened_at.elapsed() < self.reset_timeout {
+            return Err(RestError::CircuitOpen);
+        }
+        // Only one probe at a time. A probe which never reported back (for
+        // example because its future was dropped) is given up on after another
+        // reset timeout.
+        match inner.probe {
+            Some((_, started_at)) if started_at.elapsed() < self.reset_timeout => {
+                Err(RestError::CircuitOpen)
+            }
+            _ => {
+                let id = inner.next_probe;
+                inner.next_probe = id.wrapping_add(1);
+                inner.probe = Some((id, Instant::now()));
+                Ok(Permit { probe: Some(id) })
+            }
+        }
+    }
+
+    fn record(&self, permit: Permit, result: &Result<Response<Bytes>, RestError>) {
+        let success = match result {
+            Ok(rsp) => !rsp.status().is_server_error(),
+            Err(_) => false,
+        };
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        // Only the current probe finishes the probe; requests let through
+        // before the circuit opened, or a probe that was given up on, do not.
+        if permit.probe.is_some() && permit.probe == inner.probe.map(|(id, _)| id) {
+            inner.probe = None;
+        }
+        if success {
+            inner.consecutive_failures = 0;
+            inner.opened_at = None;
+        } else {
+            inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+            if inner.opened_at.is_some() || inner.consecutive_failures >= self.failure_threshold {
+                inner.opened_at = Some(Instant::now());
+            }
+        }
+    }
+}
+
+/// Run `call` through the circuit breaker, if there is one.
+#[cfg(any(feature = "reqwest-blocking", feature = "ureq-client"))]
+pub(crate) fn guard<F>(
+    breaker: Option<&CircuitBreaker>,
+    call: F,
+) -> Result<Response<Bytes>, RestError>
+where
+    F: FnOnce() -> Result<Response<Bytes>, RestError>,
+{
+    match breaker {
+        Some(breaker) => {
+            let permit = breaker.acquire()?;
+            let result = call();
+            breaker.record(permit, &result);
+            result
+        }
+        None => call(),
+    }
+}
+
+/// Await `call` through the circuit breaker, if there is one.
+#[cfg(any(feature = "reqwest-async", feature = "hyper-client", feature = "tower"))]
+pub(crate) async fn guard_async<F>(
+    breaker: Option<&CircuitBreaker>,
+    call: F,
+) -> Result<Response<Bytes>, RestError>
+where
+    F: Future<Output = Result<Response<Bytes>, RestError>>,
+{
+    match breaker {
+        Some(breaker) => {
+            let permit = breaker.acquire()?;
+            let result = call.await;
+            breaker.record(permit, &result);
+            result
+        }
+        None => call.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, thread::sleep};
+
+    use super::*;
+
+    const RESET_TIMEOUT: Duration = Duration::from_millis(50);
+
+    fn ok() -> Result<Response<Bytes>, RestError> {
+        Ok(Response::new(Bytes::new()))
+    }
+
+    fn server_error() -> Result<Response<Bytes>, RestError> {
+        Ok(Response::builder().status(503).body(Bytes::new()).unwrap())
+    }
+
+    fn transport_error() -> Result<Response<Bytes>, RestError> {
+        Err(io::Error::new(io::ErrorKind::ConnectionRefused, "refused").into())
+    }
+
+    fn send(breaker: &CircuitBreaker, result: Result<Response<Bytes>, RestError>) {
+        let permit = breaker.acquire().unwrap();
+        breaker.record(permit, &result);
+    }
+
+    fn opened(failure_threshold: u32) -> CircuitBreaker {
+        let breaker = CircuitBreaker::new(failure_threshold, RESET_TIMEOUT);
+        for _ in 0..failure_threshold {
+            send(&breaker, transport_error());
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+        breaker
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, RESET_TIMEOUT);
+        send(&breaker, transport_error());
+        send(&breaker, server_error());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        send(&breaker, transport_error());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(breaker.acquire(), Err(RestError::CircuitOpen)));
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, RESET_TIMEOUT);
+        send(&breaker, transport_error());
+        send(&breaker, ok());
+        send(&breaker, transport_error());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_lets_one_probe_through() {
+        let breaker = opened(1);
+        sleep(RESET_TIMEOUT);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        let probe = breaker.acquire().unwrap();
+        assert!(probe.probe.is_some());
+        assert!(matches!(breaker.acquire(), Err(RestError::CircuitOpen)));
+    }
+
+    #[test]
+    fn successful_probe_closes() {
+        let breaker = opened(2);
+        sleep(RESET_TIMEOUT);
+        send(&breaker, ok());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        // The failure count starts over.
+        send(&breaker, transport_error());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn failed_probe_opens_again() {
+        let breaker = opened(2);
+        sleep(RESET_TIMEOUT);
+        send(&breaker, server_error());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(breaker.acquire(), Err(RestError::CircuitOpen)));
+    }
+
+    #[test]
+    fn earlier_request_does_not_end_probe() {
+        let breaker = CircuitBreaker::new(1, RESET_TIMEOUT);
+        let earlier = breaker.acquire().unwrap();
+        send(&breaker, transport_error());
+        sleep(RESET_TIMEOUT);
+        let probe = breaker.acquire().unwrap();
+
+        breaker.record(earlier, &transport_error());
+        let inner = breaker.inner.lock().unwrap();
+        assert_eq!(inner.probe.map(|(id, _)| id), probe.probe);
+    }
+
+    #[test]
+    fn abandoned_probe_is_replaced() {
+        let breaker = opened(1);
+        sleep(RESET_TIMEOUT);
+        let abandoned = breaker.acquire().unwrap();
+        sleep(RESET_TIMEOUT);
+        let probe = breaker.acquire().unwrap();
+        assert_ne!(abandoned, probe);
+
+        breaker.record(abandoned, &transport_error());
+        assert!(matches!(breaker.acquire(), Err(RestError::CircuitOpen)));
+        breaker.record(probe, &ok());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,396 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    hash::Hash,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use http::{
+    header::AUTHORIZATION, HeaderMap, HeaderValue, Method, Request, Response, StatusCode, Version,
+};
+
+use crate::error::RestError;
+
+/// Identifies requests which may share a single upstream call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RequestKey {
+    method: Method,
+    url: String,
+    authorization: Option<HeaderValue>,
+    body: Vec<u8>,
+}
+
+impl RequestKey {
+    fn new(request: &Request<Vec<u8>>) -> Self {
+        Self {
+            method: request.method().clone(),
+            url: normalize_uri(request.uri()),
+            authorization: request.headers().get(AUTHORIZATION).cloned(),
+            body: request.body().clone(),
+        }
+    }
+}
+
+/// Sort the query parameters so that the order they were added in does not
+/// matter.
+fn normalize_uri(uri: &http::Uri) -> String {
+    let mut normalized = format!(
+        "{}://{}{}",
+        uri.scheme_str().unwrap_or_default(),
+        uri.authority().map(|a| a.as_str()).unwrap_or_default(),
+        uri.path(),
+    );
+    if let Some(query) = uri.query() {
+        let mut pairs: Vec<_> = url::form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        pairs.sort();
+        normalized.push('?');
+        normalized.push_str(
+            &url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(pairs)
+                .finish(),
+        );
+    }
+    normalized
+}
+
+#[derive(Debug, Clone)]
+struct SharedResponse {
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl From<Response<Bytes>> for SharedResponse {
+    fn from(rsp: Response<Bytes>) -> Self {
+        let (parts, body) = rsp.into_parts();
+        Self {
+            status: parts.status,
+            version: parts.version,
+            headers: parts.headers,
+            body,
+        }
+    }
+}
+
+impl SharedResponse {
+    fn into_response(self) -> Result<Response<Bytes>, RestError> {
+        let mut http_rsp = Response::builder()
+            .status(self.status)
+            .version(self.version);
+        if let Some(headers) = http_rsp.headers_mut() {
+            *headers = self.headers;
+        }
+        http_rsp.body(self.body).map_err(From::from)
+    }
+}
+
+type InFlight = Shared<BoxFuture<'static, Result<SharedResponse, Arc<RestError>>>>;
+type InFlightMap = Arc<Mutex<HashMap<RequestKey, InFlight>>>;
+
+/// A request waiting on an in-flight call. The call removes its own entry when
+/// it completes; if every waiter is dropped before that, the last one removes
+/// the entry, which also cancels the call.
+struct Waiter {
+    key: RequestKey,
+    in_flight: InFlightMap,
+    shared: InFlight,
+}
+
+impl Future for Waiter {
+    type Output = Result<SharedResponse, Arc<RestError>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.shared).poll(cx)
+    }
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Only the entry itself and this waiter are left.
+        let abandoned = in_flight
+            .get(&self.key)
+            .is_some_and(|entry| entry.ptr_eq(&self.shared) && entry.strong_count() == Some(2));
+        let removed = if abandoned {
+            in_flight.remove(&self.key)
+        } else {
+            None
+        };
+        drop(in_flight);
+        drop(removed);
+    }
+}
+
+/// Single-flight coalescing of identical concurrent requests.
+#[derive(Clone, Default)]
+pub(crate) struct Coalescer {
+    in_flight: InFlightMap,
+}
+
+impl Coalescer {
+    /// Run `call` for `request`, unless an identical request is already in
+    /// flight, in which case wait for and share its response. Only `GET` and
+    /// `HEAD` requests are coalesced, others always run `call`.
+    pub(crate) async fn run<F, Fut>(
+        &self,
+        request: Request<Vec<u8>>,
+        call: F,
+    ) -> Result<Response<Bytes>, RestError>
+    where
+        F: FnOnce(Request<Vec<u8>>) -> Fut,
+        Fut: Future<Output = Result<Response<Bytes>, RestError>> + Send + 'static,
+    {
+        if !matches!(*request.method(), Method::GET | Method::HEAD) {
+            return call(request).await;
+        }
+
+        let key = RequestKey::new(&request);
+        let shared = {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            in_flight
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    let map = self.in_flight.clone();
+                    let key = key.clone();
+                    let fut = call(request);
+                    async move {
+                        let result = fut.await.map(SharedResponse::from).map_err(Arc::new);
+                        map.lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .remove(&key);
+                        result
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+
+        let waiter = Waiter {
+            key,
+            in_flight: self.in_flight.clone(),
+            shared,
+        };
+        match waiter.await {
+            Ok(rsp) => rsp.into_response(),
+            Err(err) => {
+                Err(Arc::try_unwrap(err).unwrap_or_else(|source| RestError::Coalesced { source }))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Coalescer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let in_flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len();
+        f.debug_struct("Coalescer")
+            .field("in_flight", &in_flight)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::{channel::oneshot, executor::block_on, task::noop_waker_ref};
+
+    use super::*;
+
+    fn request(method: Method, uri: &str) -> Request<Vec<u8>> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Vec::new())
+            .unwrap()
+    }
+
+    fn poll<F>(future: &mut Pin<Box<F>>) -> Poll<F::Output>
+    where
+        F: Future,
+    {
+        future
+            .as_mut()
+            .poll(&mut Context::from_waker(noop_waker_ref()))
+    }
+
+    fn in_flight(coalescer: &Coalescer) -> usize {
+        coalescer.in_flight.lock().unwrap().len()
+    }
+
+    /// A call which counts how often it runs and answers once `release` is
+    /// sent. The call holds a clone of `token` until it is dropped.
+    struct Upstream {
+        calls: AtomicUsize,
+        release: Shared<oneshot::Receiver<()>>,
+        token: Arc<()>,
+    }
+
+    impl Upstream {
+        fn new() -> (Self, oneshot::Sender<()>) {
+            let (tx, rx) = oneshot::channel();
+            let upstream = Self {
+                calls: AtomicUsize::new(0),
+                release: rx.shared(),
+                token: Arc::new(()),
+            };
+            (upstream, tx)
+        }
+
+        fn call(
+            &self,
+        ) -> impl FnOnce(Request<Vec<u8>>) -> BoxFuture<'static, Result<Response<Bytes>, RestError>> + '_
+        {
+            move |request| {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                let release = self.release.clone();
+                let token = self.token.clone();
+                async move {
+                    let _ = release.await;
+                    drop(token);
+                    Ok(Response::new(Bytes::from(request.uri().to_string())))
+                }
+                .boxed()
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+
+        fn running(&self) -> bool {
+            Arc::strong_count(&self.token) > 1
+        }
+    }
+
+    #[test]
+    fn shares_in_flight_requests() {
+        let coalescer = Coalescer::default();
+        let (upstream, release) = Upstream::new();
+        let (first, second, ()) = block_on(async {
+            futures::join!(
+                coalescer.run(request(Method::GET, "https://x/a?b=1&c=2"), upstream.call()),
+                coalescer.run(request(Method::GET, "https://x/a?c=2&b=1"), upstream.call()),
+                async {
+                    release.send(()).unwrap();
+                },
+            )
+        });
+        assert_eq!(upstream.calls(), 1);
+        assert_eq!(first.unwrap().body(), "https://x/a?b=1&c=2");
+        assert_eq!(second.unwrap().body(), "https://x/a?b=1&c=2");
+        assert_eq!(in_flight(&coalescer), 0);
+    }
+
+    #[test]
+    fn runs_again_once_completed() {
+        let coalescer = Coalescer::default();
+        let (upstream, release) = Upstream::new();
+        release.send(()).unwrap();
+        for _ in 0..2 {
+            block_on(coalescer.run(request(Method::GET, "https://x/a"), upstream.call())).unwrap();
+        }
+        assert_eq!(upstream.calls(), 2);
+    }
+
+    #[test]
+    fn dropping_one_waiter_keeps_the_call() {
+        let coalescer = Coalescer::default();
+        let (upstream, release) = Upstream::new();
+        let mut first =
+            Box::pin(coalescer.run(request(Method::GET, "https://x/a"), upstream.call()));
+        let mut second =
+            Box::pin(coalescer.run(request(Method::GET, "https://x/a"), upstream.call()));
+        assert!(poll(&mut first).is_pending());
+        assert!(poll(&mut second).is_pending());
+
+        drop(first);
+        assert_eq!(in_flight(&coalescer), 1);
+        assert!(upstream.running());
+
+        release.send(()).unwrap();
+        match poll(&mut second) {
+            Poll::Ready(rsp) => assert_eq!(rsp.unwrap().body(), "https://x/a"),
+            Poll::Pending => panic!("the call was released"),
+        }
+        assert_eq!(upstream.calls(), 1);
+        assert_eq!(in_flight(&coalescer), 0);
+    }
+
+    #[test]
+    fn dropping_every_waiter_cancels_the_call() {
+        let coalescer = Coalescer::default();
+        let (upstream, _release) = Upstream::new();
+        let mut first =
+            Box::pin(coalescer.run(request(Method::GET, "https://x/a"), upstream.call()));
+        let mut second =
+            Box::pin(coalescer.run(request(Method::GET, "https://x/a"), upstream.call()));
+        assert!(poll(&mut first).is_pending());
+        assert!(poll(&mut second).is_pending());
+
+        drop(first);
+        drop(second);
+        assert_eq!(in_flight(&coalescer), 0);
+        assert!(!upstream.running());
+
+        let mut third =
+            Box::pin(coalescer.run(request(Method::GET, "https://x/a"), upstream.call()));
+        assert!(poll(&mut third).is_pending());
+        assert_eq!(upstream.calls(), 2);
+    }
+
+    #[test]
+    fn does_not_coalesce_other_methods() {
+        let coalescer = Coalescer::default();
+        let (upstream, release) = Upstream::new();
+        let mut first =
+            Box::pin(coalescer.run(request(Method::POST, "https://x/a"), upstream.call()));
+        let mut second =
+            Box::pin(coalescer.run(request(Method::POST, "https://x/a"), upstream.call()));
+        assert!(poll(&mut first).is_pending());
+        assert!(poll(&mut second).is_pending());
+        assert_eq!(upstream.calls(), 2);
+        assert_eq!(in_flight(&coalescer), 0);
+
+        release.send(()).unwrap();
+        assert!(matches!(poll(&mut first), Poll::Ready(Ok(_))));
+        assert!(matches!(poll(&mut second), Poll::Ready(Ok(_))));
+    }
+
+    #[test]
+    fn does_not_share_between_credentials() {
+        let coalescer = Coalescer::default();
+        let (upstream, _release) = Upstream::new();
+        let with_token = |token: &'static str| {
+            let mut request = request(Method::GET, "https://x/a");
+            request
+                .headers_mut()
+                .insert(AUTHORIZATION, HeaderValue::from_static(token));
+            request
+        };
+        let mut first = Box::pin(coalescer.run(with_token("Bearer a"), upstream.call()));
+        let mut second = Box::pin(coalescer.run(with_token("Bearer b"), upstream.call()));
+        assert!(poll(&mut first).is_pending());
+        assert!(poll(&mut second).is_pending());
+        assert_eq!(upstream.calls(), 2);
+        assert_eq!(in_flight(&coalescer), 2);
+    }
+}
@@ -0,0 +1,217 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::{future::Either, TryFutureExt};
+use futures_timer::Delay;
+use http::header::{HeaderValue, USER_AGENT};
+use hyper::{client::HttpConnector, Body};
+use hyper_tls::HttpsConnector;
+use log::debug;
+use url::Url;
+
+use super::{
+    backend::{
+        auth,
+        circuit::{guard_async, CircuitBreaker},
+        rest_url, Auth,
+    },
+    CircuitState, HttpOptions, ItadApiBuilder,
+};
+use crate::{
+    api,
+    auth::Scope,
+    error::{ItadApiResult, RestError},
+};
+
+pub type HyperHttpsClient = hyper::Client<HttpsConnector<HttpConnector>, Body>;
+
+/// An asynchronous client backed by `hyper`.
+#[derive(Clone, Debug)]
+pub struct ItadApiClientHyper {
+    client: HyperHttpsClient,
+    rest_url: Url,
+    auth: Auth,
+    user_agent: Option<HeaderValue>,
+    timeout: Option<Duration>,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+impl ItadApiClientHyper {
+    pub fn new() -> ItadApiResult<Self> {
+        Self::new_impl::<&str, &str>(HttpOptions::default().build_hyper(), None, None, None)
+    }
+
+    pub fn with_api_key<S>(api_key: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(
+            HttpOptions::default().build_hyper(),
+            None,
+            Some(api_key),
+            None::<&str>,
+        )
+    }
+
+    pub fn with_oauth_token<S>(oauth_token: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(
+            HttpOptions::default().build_hyper(),
+            None,
+            None::<&str>,
+            Some(oauth_token),
+        )
+    }
+
+    pub fn builder() -> ItadApiBuilder {
+        ItadApiBuilder::new()
+    }
+
+    fn new_impl<K, T>(
+        client: HyperHttpsClient,
+        host: Option<&str>,
+        api_key: Option<K>,
+        oauth_token: Option<T>,
+    ) -> ItadApiResult<Self>
+    where
+        K: Into<String>,
+        T: Into<String>,
+    {
+        Ok(ItadApiClientHyper {
+            client,
+            rest_url: rest_url(host)?,
+            auth: auth(api_key, oauth_token),
+            user_agent: None,
+            timeout: None,
+            circuit_breaker: None,
+        })
+    }
+
+    /// The state of the circuit breaker, if one was configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::state)
+    }
+}
+
+impl api::RestClient for ItadApiClientHyper {
+    type Error = RestError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
+        debug!("REST api call {}", endpoint);
+        self.rest_url
+            .join(endpoint.trim_start_matches('/'))
+            .map_err(From::from)
+    }
+
+    fn append_api_key_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_api_key_query_param(query_params)?)
+    }
+
+    fn append_oauth_token_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_oauth_token_query_param(query_params)?)
+    }
+
+    fn oauth_bearer_header(&self) -> Result<http::HeaderValue, api::ApiError<Self::Error>> {
+        Ok(self.auth.oauth_bearer_header()?)
+    }
+
+    fn oauth_token_scopes(&self) -> Option<&[Scope]> {
+        self.auth.oauth_scopes.as_deref()
+    }
+}
+
+#[async_trait]
+impl api::AsyncClient for ItadApiClientHyper {
+    async fn rest_async(
+        &self,
+        request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<http::Response<bytes::Bytes>, api::ApiError<Self::Error>> {
+        let call = || async {
+            let request = match &self.user_agent {
+                Some(user_agent) => request.header(USER_AGENT, user_agent.clone()),
+                None => request,
+            };
+            let http_request = request.body(Body::from(body))?;
+            let send = async {
+                let rsp = self.client.request(http_request).await?;
+
+                let (parts, body) = rsp.into_parts();
+                let body = hyper::body::to_bytes(body).await?;
+                Ok(http::Response::from_parts(parts, body))
+            };
+            // hyper has no total timeout of its own.
+            match self.timeout {
+                Some(timeout) => {
+                    futures::pin_mut!(send);
+                    match futures::future::select(send, Delay::new(timeout)).await {
+                        Either::Left((result, _)) => result,
+                        Either::Right(_) => Err(RestError::Timeout),
+                    }
+                }
+                None => send.await,
+            }
+        };
+        guard_async(self.circuit_breaker.as_ref(), call())
+            .map_err(api::ApiError::client)
+            .await
+    }
+}
+
+impl HttpOptions {
+    fn build_hyper(&self) -> HyperHttpsClient {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        http.set_connect_timeout(self.connect_timeout);
+        let mut builder = hyper::Client::builder();
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder.pool_idle_timeout(timeout);
+        }
+        builder.build(HttpsConnector::new_with_connector(http))
+    }
+}
+
+impl ItadApiBuilder {
+    /// Use a pre-configured `hyper` client. Transport options set on this
+    /// builder, other than the user agent and timeout, are ignored by
+    /// `build_hyper` when a client is supplied.
+    pub fn hyper_client(&mut self, client: HyperHttpsClient) -> &mut Self {
+        self.hyper_client = Some(client);
+        self
+    }
+
+    pub fn build_hyper(&self) -> ItadApiResult<ItadApiClientHyper> {
+        let client = match &self.hyper_client {
+            Some(client) => client.clone(),
+            None => self.options.build_hyper(),
+        };
+        let mut api = ItadApiClientHyper::new_impl(
+            client,
+            self.host.as_deref(),
+            self.api_key.as_ref(),
+            self.oauth_token.as_ref(),
+        )?;
+        api.auth.oauth_scopes = self.oauth_scopes.clone();
+        api.circuit_breaker = self.new_circuit_breaker();
+        api.timeout = self.options.timeout;
+        api.user_agent = self
+            .options
+            .user_agent
+            .as_deref()
+            .map(HeaderValue::from_str)
+            .transpose()
+            .map_err(|err| api::ApiError::client(RestError::from(http::Error::from(err))))?;
+        Ok(api)
+    }
+}
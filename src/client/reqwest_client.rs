@@ -0,0 +1,429 @@
+use std::convert::TryInto;
+
+#[cfg(feature = "reqwest-async")]
+use async_trait::async_trait;
+use log::debug;
+#[cfg(feature = "reqwest-blocking")]
+use reqwest::blocking::{Client as HttpClient, ClientBuilder as HttpClientBuilder};
+#[cfg(feature = "reqwest-async")]
+use reqwest::{Client as AsyncHttpClient, ClientBuilder as AsyncHttpClientBuilder};
+use url::Url;
+
+#[cfg(feature = "reqwest-blocking")]
+use super::backend::circuit::guard;
+#[cfg(feature = "reqwest-async")]
+use super::backend::circuit::guard_async;
+#[cfg(feature = "reqwest-async")]
+use super::coalesce::Coalescer;
+use super::{
+    backend::{auth, circuit::CircuitBreaker, rest_url, Auth},
+    CircuitState, HttpOptions, ItadApiBuilder,
+};
+use crate::{
+    api,
+    auth::Scope,
+    error::{ItadApiResult, RestError},
+};
+
+#[cfg(feature = "reqwest-blocking")]
+#[derive(Clone, Debug)]
+pub struct ItadApiClient {
+    client: HttpClient,
+    rest_url: Url,
+    auth: Auth,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+#[cfg(feature = "reqwest-blocking")]
+impl ItadApiClient {
+    pub fn new() -> ItadApiResult<Self> {
+        Self::new_impl::<&str, &str>(HttpClient::new(), None, None, None)
+    }
+
+    pub fn with_api_key<S>(api_key: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(HttpClient::new(), None, Some(api_key), None::<&str>)
+    }
+
+    pub fn with_oauth_token<S>(oauth_token: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(HttpClient::new(), None, None::<&str>, Some(oauth_token))
+    }
+
+    fn new_impl<K, T>(
+        client: HttpClient,
+        host: Option<&str>,
+        api_key: Option<K>,
+        oauth_token: Option<T>,
+    ) -> ItadApiResult<Self>
+    where
+        K: Into<String>,
+        T: Into<String>,
+    {
+        Ok(ItadApiClient {
+            client,
+            rest_url: rest_url(host)?,
+            auth: auth(api_key, oauth_token),
+            circuit_breaker: None,
+        })
+    }
+
+    pub fn builder() -> ItadApiBuilder {
+        ItadApiBuilder::new()
+    }
+
+    /// The state of the circuit breaker, if one was configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::state)
+    }
+}
+
+#[cfg(feature = "reqwest-blocking")]
+impl api::RestClient for ItadApiClient {
+    type Error = RestError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
+        debug!("REST api call {}", endpoint);
+        self.rest_url
+            .join(endpoint.trim_start_matches('/'))
+            .map_err(From::from)
+    }
+
+    fn append_api_key_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_api_key_query_param(query_params)?)
+    }
+
+    fn append_oauth_token_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_oauth_token_query_param(query_params)?)
+    }
+
+    fn oauth_bearer_header(&self) -> Result<http::HeaderValue, api::ApiError<Self::Error>> {
+        Ok(self.auth.oauth_bearer_header()?)
+    }
+
+    fn oauth_token_scopes(&self) -> Option<&[Scope]> {
+        self.auth.oauth_scopes.as_deref()
+    }
+}
+
+#[cfg(feature = "reqwest-blocking")]
+impl api::Client for ItadApiClient {
+    fn rest(
+        &self,
+        request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<http::Response<bytes::Bytes>, api::ApiError<Self::Error>> {
+        let call = || -> Result<_, RestError> {
+            let http_request = request.body(body)?;
+            let request = http_request.try_into()?;
+            let rsp = self.client.execute(request)?;
+
+            let mut http_rsp = http::Response::builder()
+                .status(rsp.status())
+                .version(rsp.version());
+            let headers = http_rsp.headers_mut().unwrap();
+            for (key, val) in rsp.headers() {
+                headers.insert(key, val.clone());
+            }
+            http_rsp.body(rsp.bytes()?).map_err(From::from)
+        };
+        guard(self.circuit_breaker.as_ref(), call).map_err(api::ApiError::client)
+    }
+}
+
+#[cfg(feature = "reqwest-async")]
+#[derive(Clone, Debug)]
+pub struct ItadApiClientAsync {
+    client: AsyncHttpClient,
+    rest_url: Url,
+    auth: Auth,
+    coalescer: Option<Coalescer>,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+#[cfg(feature = "reqwest-async")]
+impl ItadApiClientAsync {
+    pub fn new() -> ItadApiResult<Self> {
+        Self::new_impl::<&str, &str>(AsyncHttpClient::new(), None, None, None)
+    }
+
+    pub fn with_api_key<S>(api_key: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(AsyncHttpClient::new(), None, Some(api_key), None::<&str>)
+    }
+
+    pub fn with_oauth_token<S>(oauth_token: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(
+            AsyncHttpClient::new(),
+            None,
+            None::<&str>,
+            Some(oauth_token),
+        )
+    }
+
+    pub fn builder() -> ItadApiBuilder {
+        ItadApiBuilder::new()
+    }
+
+    fn new_impl<K, T>(
+        client: AsyncHttpClient,
+        host: Option<&str>,
+        api_key: Option<K>,
+        oauth_token: Option<T>,
+    ) -> ItadApiResult<Self>
+    where
+        K: Into<String>,
+        T: Into<String>,
+    {
+        let api = Self {
+            client,
+            rest_url: rest_url(host)?,
+            auth: auth(api_key, oauth_token),
+            circuit_breaker: None,
+            coalescer: None,
+        };
+        Ok(api)
+    }
+
+    /// The state of the circuit breaker, if one was configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::state)
+    }
+
+    /// Run many queries with bounded concurrency. See [`api::batch`].
+    pub fn batch<I>(&self, endpoints: I) -> api::Batch<'_, Self, I::Item>
+    where
+        I: IntoIterator,
+    {
+        api::batch(self, endpoints)
+    }
+}
+
+#[cfg(feature = "reqwest-async")]
+impl api::RestClient for ItadApiClientAsync {
+    type Error = RestError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
+        debug!("REST api call {}", endpoint);
+        self.rest_url
+            .join(endpoint.trim_start_matches('/'))
+            .map_err(From::from)
+    }
+
+    fn append_api_key_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_api_key_query_param(query_params)?)
+    }
+
+    fn append_oauth_token_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_oauth_token_query_param(query_params)?)
+    }
+
+    fn oauth_bearer_header(&self) -> Result<http::HeaderValue, api::ApiError<Self::Error>> {
+        Ok(self.auth.oauth_bearer_header()?)
+    }
+
+    fn oauth_token_scopes(&self) -> Option<&[Scope]> {
+        self.auth.oauth_scopes.as_deref()
+    }
+}
+
+#[cfg(feature = "reqwest-async")]
+#[async_trait]
+impl api::AsyncClient for ItadApiClientAsync {
+    async fn rest_async(
+        &self,
+        request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<http::Response<bytes::Bytes>, api::ApiError<Self::Error>> {
+        let http_request = request
+            .body(body)
+            .map_err(|err| api::ApiError::client(err.into()))?;
+        // Behind the coalescer only the shared call goes through the circuit
+        // breaker, so that its result is recorded once however many requests
+        // wait on it.
+        match &self.coalescer {
+            Some(coalescer) => {
+                let client = self.client.clone();
+                let circuit_breaker = self.circuit_breaker.clone();
+                coalescer
+                    .run(http_request, |http_request| async move {
+                        guard_async(
+                            circuit_breaker.as_ref(),
+                            execute_async(&client, http_request),
+                        )
+                        .await
+                    })
+                    .await
+            }
+            None => {
+                guard_async(
+                    self.circuit_breaker.as_ref(),
+                    execute_async(&self.client, http_request),
+                )
+                .await
+            }
+        }
+        .map_err(api::ApiError::client)
+    }
+}
+
+#[cfg(feature = "reqwest-async")]
+async fn execute_async(
+    client: &AsyncHttpClient,
+    http_request: http::Request<Vec<u8>>,
+) -> Result<http::Response<bytes::Bytes>, RestError> {
+    let request = http_request.try_into()?;
+    let rsp = client.execute(request).await?;
+
+    let mut http_rsp = http::Response::builder()
+        .status(rsp.status())
+        .version(rsp.version());
+    let headers = http_rsp.headers_mut().unwrap();
+    for (key, val) in rsp.headers() {
+        headers.insert(key, val.clone());
+    }
+    http_rsp.body(rsp.bytes().await?).map_err(From::from)
+}
+
+/// Apply `HttpOptions` to a blocking or async `ClientBuilder`, which share
+/// method names but no trait.
+macro_rules! configure {
+    ($builder:ident, $options:expr) => {{
+        if let Some(timeout) = $options.timeout {
+            $builder = $builder.timeout(timeout);
+        }
+        if let Some(timeout) = $options.connect_timeout {
+            $builder = $builder.connect_timeout(timeout);
+        }
+        for proxy in &$options.proxies {
+            $builder = $builder.proxy(proxy.clone());
+        }
+        if $options.no_proxy {
+            $builder = $builder.no_proxy();
+        }
+        for cert in &$options.root_certificates {
+            $builder = $builder.add_root_certificate(cert.clone());
+        }
+        if let Some(enable) = $options.tls_built_in_root_certs {
+            $builder = $builder.tls_built_in_root_certs(enable);
+        }
+        #[cfg(feature = "gzip")]
+        if let Some(enable) = $options.gzip {
+            $builder = $builder.gzip(enable);
+        }
+        #[cfg(feature = "brotli")]
+        if let Some(enable) = $options.brotli {
+            $builder = $builder.brotli(enable);
+        }
+        if let Some(max) = $options.pool_max_idle_per_host {
+            $builder = $builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = $options.pool_idle_timeout {
+            $builder = $builder.pool_idle_timeout(timeout);
+        }
+        if let Some(user_agent) = &$options.user_agent {
+            $builder = $builder.user_agent(user_agent);
+        }
+        $builder
+    }};
+}
+
+impl HttpOptions {
+    #[cfg(feature = "reqwest-blocking")]
+    fn build_blocking(&self) -> ItadApiResult<HttpClient> {
+        let mut builder = HttpClientBuilder::new();
+        Ok(configure!(builder, self).build()?)
+    }
+
+    #[cfg(feature = "reqwest-async")]
+    fn build_async(&self) -> ItadApiResult<AsyncHttpClient> {
+        let mut builder = AsyncHttpClientBuilder::new();
+        Ok(configure!(builder, self).build()?)
+    }
+}
+
+impl ItadApiBuilder {
+    /// Use a pre-configured blocking client. Transport options set on this
+    /// builder are ignored by `build` when a client is supplied.
+    #[cfg(feature = "reqwest-blocking")]
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Use a pre-configured async client. Transport options set on this
+    /// builder are ignored by `build_async` when a client is supplied.
+    #[cfg(feature = "reqwest-async")]
+    pub fn async_http_client(&mut self, client: AsyncHttpClient) -> &mut Self {
+        self.async_http_client = Some(client);
+        self
+    }
+
+    /// Share a single upstream call between identical concurrent `GET` and
+    /// `HEAD` requests made by the client from `build_async`. Requests are
+    /// identical if their method, URL (ignoring query parameter order),
+    /// `Authorization` header and body match.
+    #[cfg(feature = "reqwest-async")]
+    pub fn coalesce_requests(&mut self, enable: bool) -> &mut Self {
+        self.coalesce_requests = enable;
+        self
+    }
+
+    #[cfg(feature = "reqwest-blocking")]
+    pub fn build(&self) -> ItadApiResult<ItadApiClient> {
+        let client = match &self.http_client {
+            Some(client) => client.clone(),
+            None => self.options.build_blocking()?,
+        };
+        let mut api = ItadApiClient::new_impl(
+            client,
+            self.host.as_deref(),
+            self.api_key.as_ref(),
+            self.oauth_token.as_ref(),
+        )?;
+        api.auth.oauth_scopes = self.oauth_scopes.clone();
+        api.circuit_breaker = self.new_circuit_breaker();
+        Ok(api)
+    }
+
+    #[cfg(feature = "reqwest-async")]
+    pub fn build_async(&self) -> ItadApiResult<ItadApiClientAsync> {
+        let client = match &self.async_http_client {
+            Some(client) => client.clone(),
+            None => self.options.build_async()?,
+        };
+        let mut api = ItadApiClientAsync::new_impl(
+            client,
+            self.host.as_deref(),
+            self.api_key.as_ref(),
+            self.oauth_token.as_ref(),
+        )?;
+        api.auth.oauth_scopes = self.oauth_scopes.clone();
+        api.circuit_breaker = self.new_circuit_breaker();
+        if self.coalesce_requests {
+            api.coalescer = Some(Coalescer::default());
+        }
+        Ok(api)
+    }
+}
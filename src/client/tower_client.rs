@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::TryFutureExt;
+use log::debug;
+use tower::{BoxError, Service, ServiceExt};
+use url::Url;
+
+use super::{
+    backend::{
+        auth,
+        circuit::{guard_async, CircuitBreaker},
+        rest_url, Auth,
+    },
+    CircuitState, ItadApiBuilder,
+};
+use crate::{
+    api,
+    auth::Scope,
+    error::{ItadApiResult, RestError},
+};
+
+/// An asynchronous client which sends its requests through a `tower`
+/// service, so that middleware such as timeouts, retries or rate limiting can
+/// be layered on top of the transport.
+#[derive(Clone, Debug)]
+pub struct ItadApiClientTower<S> {
+    service: S,
+    rest_url: Url,
+    auth: Auth,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+impl<S> ItadApiClientTower<S> {
+    pub fn new(service: S) -> ItadApiResult<Self> {
+        Self::new_impl::<&str, &str>(service, None, None, None)
+    }
+
+    pub fn with_api_key<K>(service: S, api_key: K) -> ItadApiResult<Self>
+    where
+        K: Into<String>,
+    {
+        Self::new_impl(service, None, Some(api_key), None::<&str>)
+    }
+
+    pub fn with_oauth_token<T>(service: S, oauth_token: T) -> ItadApiResult<Self>
+    where
+        T: Into<String>,
+    {
+        Self::new_impl(service, None, None::<&str>, Some(oauth_token))
+    }
+
+    fn new_impl<K, T>(
+        service: S,
+        host: Option<&str>,
+        api_key: Option<K>,
+        oauth_token: Option<T>,
+    ) -> ItadApiResult<Self>
+    where
+        K: Into<String>,
+        T: Into<String>,
+    {
+        Ok(ItadApiClientTower {
+            service,
+            rest_url: rest_url(host)?,
+            auth: auth(api_key, oauth_token),
+            circuit_breaker: None,
+        })
+    }
+
+    /// The state of the circuit breaker, if one was configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::state)
+    }
+}
+
+impl<S> api::RestClient for ItadApiClientTower<S> {
+    type Error = RestError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
+        debug!("REST api call {}", endpoint);
+        self.rest_url
+            .join(endpoint.trim_start_matches('/'))
+            .map_err(From::from)
+    }
+
+    fn append_api_key_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_api_key_query_param(query_params)?)
+    }
+
+    fn append_oauth_token_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_oauth_token_query_param(query_params)?)
+    }
+
+    fn oauth_bearer_header(&self) -> Result<http::HeaderValue, api::ApiError<Self::Error>> {
+        Ok(self.auth.oauth_bearer_header()?)
+    }
+
+    fn oauth_token_scopes(&self) -> Option<&[Scope]> {
+        self.auth.oauth_scopes.as_deref()
+    }
+}
+
+#[async_trait]
+impl<S> api::AsyncClient for ItadApiClientTower<S>
+where
+    S: Service<http::Request<Vec<u8>>, Response = http::Response<Bytes>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+{
+    async fn rest_async(
+        &self,
+        request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<http::Response<Bytes>, api::ApiError<Self::Error>> {
+        let call = || async {
+            let http_request = request.body(body)?;
+            self.service
+                .clone()
+                .oneshot(http_request)
+                .await
+                .map_err(|err| RestError::Service { source: err.into() })
+        };
+        guard_async(self.circuit_breaker.as_ref(), call())
+            .map_err(api::ApiError::client)
+            .await
+    }
+}
+
+impl ItadApiBuilder {
+    /// Build a client which sends its requests through `service`. Transport
+    /// options set on this builder are ignored.
+    ///
+    /// Retry middleware can report how often it retried a request by
+    /// inserting [`api::Retries`] into the response extensions.
+    pub fn build_tower<S>(&self, service: S) -> ItadApiResult<ItadApiClientTower<S>> {
+        let mut api = ItadApiClientTower::new_impl(
+            service,
+            self.host.as_deref(),
+            self.api_key.as_ref(),
+            self.oauth_token.as_ref(),
+        )?;
+        api.auth.oauth_scopes = self.oauth_scopes.clone();
+        api.circuit_breaker = self.new_circuit_breaker();
+        Ok(api)
+    }
+}
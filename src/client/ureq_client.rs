@@ -0,0 +1,199 @@
+use std::io::Read;
+
+use http::header::{HeaderName, HeaderValue};
+use log::debug;
+use ureq::{Agent, AgentBuilder};
+use url::Url;
+
+use super::{
+    backend::{
+        auth,
+        circuit::{guard, CircuitBreaker},
+        rest_url, Auth,
+    },
+    CircuitState, HttpOptions, ItadApiBuilder,
+};
+use crate::{
+    api,
+    auth::Scope,
+    error::{ItadApiResult, RestError},
+};
+
+/// A blocking client backed by `ureq`.
+#[derive(Clone, Debug)]
+pub struct ItadApiClientUreq {
+    agent: Agent,
+    rest_url: Url,
+    auth: Auth,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+impl ItadApiClientUreq {
+    pub fn new() -> ItadApiResult<Self> {
+        Self::new_impl::<&str, &str>(Agent::new(), None, None, None)
+    }
+
+    pub fn with_api_key<S>(api_key: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(Agent::new(), None, Some(api_key), None::<&str>)
+    }
+
+    pub fn with_oauth_token<S>(oauth_token: S) -> ItadApiResult<Self>
+    where
+        S: Into<String>,
+    {
+        Self::new_impl(Agent::new(), None, None::<&str>, Some(oauth_token))
+    }
+
+    pub fn builder() -> ItadApiBuilder {
+        ItadApiBuilder::new()
+    }
+
+    fn new_impl<K, T>(
+        agent: Agent,
+        host: Option<&str>,
+        api_key: Option<K>,
+        oauth_token: Option<T>,
+    ) -> ItadApiResult<Self>
+    where
+        K: Into<String>,
+        T: Into<String>,
+    {
+        Ok(ItadApiClientUreq {
+            agent,
+            rest_url: rest_url(host)?,
+            auth: auth(api_key, oauth_token),
+            circuit_breaker: None,
+        })
+    }
+
+    /// The state of the circuit breaker, if one was configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::state)
+    }
+}
+
+impl api::RestClient for ItadApiClientUreq {
+    type Error = RestError;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
+        debug!("REST api call {}", endpoint);
+        self.rest_url
+            .join(endpoint.trim_start_matches('/'))
+            .map_err(From::from)
+    }
+
+    fn append_api_key_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_api_key_query_param(query_params)?)
+    }
+
+    fn append_oauth_token_query_param(
+        &self,
+        query_params: &mut url::form_urlencoded::Serializer<'_, url::UrlQuery<'_>>,
+    ) -> Result<(), api::ApiError<Self::Error>> {
+        Ok(self.auth.append_oauth_token_query_param(query_params)?)
+    }
+
+    fn oauth_bearer_header(&self) -> Result<http::HeaderValue, api::ApiError<Self::Error>> {
+        Ok(self.auth.oauth_bearer_header()?)
+    }
+
+    fn oauth_token_scopes(&self) -> Option<&[Scope]> {
+        self.auth.oauth_scopes.as_deref()
+    }
+}
+
+impl api::Client for ItadApiClientUreq {
+    fn rest(
+        &self,
+        request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<http::Response<bytes::Bytes>, api::ApiError<Self::Error>> {
+        let call = || -> Result<_, RestError> {
+            let (parts, body) = request.body(body)?.into_parts();
+            let mut req = self
+                .agent
+                .request(parts.method.as_str(), &parts.uri.to_string());
+            for (key, val) in &parts.headers {
+                if let Ok(val) = val.to_str() {
+                    req = req.set(key.as_str(), val);
+                }
+            }
+            let result = if body.is_empty() {
+                req.call()
+            } else {
+                req.send_bytes(&body)
+            };
+            // ureq reports non-2xx statuses as errors, but ITAD sends a JSON
+            // body with those which the caller needs to see.
+            let rsp = match result {
+                Ok(rsp) | Err(ureq::Error::Status(_, rsp)) => rsp,
+                Err(err) => return Err(Box::new(err).into()),
+            };
+
+            let mut http_rsp = http::Response::builder().status(rsp.status());
+            let headers = http_rsp.headers_mut().unwrap();
+            for name in rsp.headers_names() {
+                if let Some(val) = rsp.header(&name) {
+                    headers.append(
+                        HeaderName::from_bytes(name.as_bytes()).map_err(http::Error::from)?,
+                        HeaderValue::from_str(val).map_err(http::Error::from)?,
+                    );
+                }
+            }
+            let mut bytes = Vec::new();
+            rsp.into_reader().read_to_end(&mut bytes)?;
+            http_rsp.body(bytes.into()).map_err(From::from)
+        };
+        guard(self.circuit_breaker.as_ref(), call).map_err(api::ApiError::client)
+    }
+}
+
+impl HttpOptions {
+    fn build_ureq(&self) -> Agent {
+        let mut builder = AgentBuilder::new();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.timeout_connect(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.max_idle_connections_per_host(max);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        builder.build()
+    }
+}
+
+impl ItadApiBuilder {
+    /// Use a pre-configured `ureq` agent. Transport options set on this
+    /// builder are ignored by `build_ureq` when an agent is supplied.
+    pub fn ureq_agent(&mut self, agent: Agent) -> &mut Self {
+        self.ureq_agent = Some(agent);
+        self
+    }
+
+    pub fn build_ureq(&self) -> ItadApiResult<ItadApiClientUreq> {
+        let agent = match &self.ureq_agent {
+            Some(agent) => agent.clone(),
+            None => self.options.build_ureq(),
+        };
+        let mut api = ItadApiClientUreq::new_impl(
+            agent,
+            self.host.as_deref(),
+            self.api_key.as_ref(),
+            self.oauth_token.as_ref(),
+        )?;
+        api.auth.oauth_scopes = self.oauth_scopes.clone();
+        api.circuit_breaker = self.new_circuit_breaker();
+        Ok(api)
+    }
+}
@@ -17,16 +17,51 @@ pub enum ItadApiError {
         #[from]
         source: url::ParseError,
     },
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+    #[error("http client error: {}", source)]
+    HttpClient {
+        #[from]
+        source: reqwest::Error,
+    },
 }
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum RestError {
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
     #[error("communication: {}", source)]
     Communication {
         #[from]
         source: reqwest::Error,
     },
+    #[cfg(feature = "ureq-client")]
+    #[error("communication: {}", source)]
+    Ureq {
+        #[from]
+        source: Box<ureq::Error>,
+    },
+    #[cfg(feature = "hyper-client")]
+    #[error("communication: {}", source)]
+    Hyper {
+        #[from]
+        source: hyper::Error,
+    },
+    #[cfg(feature = "tower")]
+    #[error("service error: {}", source)]
+    Service { source: tower::BoxError },
+    #[cfg(feature = "reqwest-async")]
+    #[error("coalesced request failed: {}", source)]
+    Coalesced { source: std::sync::Arc<RestError> },
+    #[cfg(feature = "hyper-client")]
+    #[error("request timed out")]
+    Timeout,
+    #[error("circuit breaker is open")]
+    CircuitOpen,
+    #[error("io error: {}", source)]
+    Io {
+        #[from]
+        source: std::io::Error,
+    },
     #[error("http error: {}", source)]
     Http {
         #[from]
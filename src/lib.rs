@@ -9,5 +9,16 @@ pub(crate) mod auth;
 mod client;
 mod error;
 
-pub use client::{ItadApiBuilder, ItadApiClient, ItadApiClientAsync};
+pub use auth::Scope;
+#[cfg(feature = "reqwest-blocking")]
+pub use client::ItadApiClient;
+#[cfg(feature = "reqwest-async")]
+pub use client::ItadApiClientAsync;
+#[cfg(feature = "tower")]
+pub use client::ItadApiClientTower;
+#[cfg(feature = "ureq-client")]
+pub use client::ItadApiClientUreq;
+pub use client::{CircuitState, ItadApiBuilder};
+#[cfg(feature = "hyper-client")]
+pub use client::{HyperHttpsClient, ItadApiClientHyper};
 pub use error::{ItadApiError, ItadApiResult};